@@ -0,0 +1,98 @@
+//! Authoring back-off of [`super::service`], with Prometheus metrics showing when the node
+//! holds back its blocks.
+
+use node_template_runtime::BlockNumber;
+use sc_consensus_slots::{BackoffAuthoringBlocksStrategy, BackoffAuthoringOnFinalizedHeadLagging};
+use sp_consensus_aura::Slot;
+use substrate_prometheus_endpoint::{register, Counter, Gauge, PrometheusError, Registry, U64};
+
+use crate::chain_spec::BackoffAuthoringSettings;
+
+#[derive(Clone)]
+struct Metrics {
+	backing_off: Gauge<U64>,
+	skipped_slots: Counter<U64>,
+	unfinalized_blocks: Gauge<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			backing_off: register(
+				Gauge::new(
+					"authoring_backoff_active",
+					"Whether the node skipped its last slot because finality lags behind",
+				)?,
+				registry,
+			)?,
+			skipped_slots: register(
+				Counter::new(
+					"authoring_backoff_skipped_slots_total",
+					"Number of own slots skipped because finality lags behind",
+				)?,
+				registry,
+			)?,
+			unfinalized_blocks: register(
+				Gauge::new(
+					"authoring_backoff_unfinalized_blocks",
+					"Number of blocks between the best and the finalized block at the last own slot",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// [`BackoffAuthoringOnFinalizedHeadLagging`] that reports its decisions to Prometheus.
+pub struct MeteredBackoff {
+	inner: BackoffAuthoringOnFinalizedHeadLagging<BlockNumber>,
+	metrics: Option<Metrics>,
+}
+
+impl MeteredBackoff {
+	/// Creates the strategy for `settings`, registering its metrics in `registry` if given.
+	pub fn new(
+		settings: BackoffAuthoringSettings,
+		registry: Option<&Registry>,
+	) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			inner: BackoffAuthoringOnFinalizedHeadLagging {
+				max_interval: settings.max_interval,
+				unfinalized_slack: settings.unfinalized_slack,
+				authoring_bias: settings.authoring_bias,
+			},
+			metrics: registry.map(Metrics::register).transpose()?,
+		})
+	}
+}
+
+impl BackoffAuthoringBlocksStrategy<BlockNumber> for MeteredBackoff {
+	fn should_backoff(
+		&self,
+		chain_head_number: BlockNumber,
+		chain_head_slot: Slot,
+		finalized_number: BlockNumber,
+		slot_now: Slot,
+		logging_target: &str,
+	) -> bool {
+		let backoff = self.inner.should_backoff(
+			chain_head_number,
+			chain_head_slot,
+			finalized_number,
+			slot_now,
+			logging_target,
+		);
+
+		if let Some(metrics) = &self.metrics {
+			metrics.backing_off.set(backoff as u64);
+			metrics
+				.unfinalized_blocks
+				.set(chain_head_number.saturating_sub(finalized_number).into());
+			if backoff {
+				metrics.skipped_slots.inc();
+			}
+		}
+
+		backoff
+	}
+}
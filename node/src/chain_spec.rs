@@ -1,18 +1,161 @@
+use crate::generate_spec::{read_authorities, GenesisDescription};
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	apis::TokenMetadata, impls::token_metadata, opaque::SessionKeys, AccountId, AuraConfig,
+	Balance, BalancesConfig, BlockNumber, CouncilMembershipConfig, GenesisConfig, GrandpaConfig,
+	SessionConfig, Signature, SystemConfig, TemplateModuleConfig, ValidatorSetConfig,
+	VestingConfig, DAYS, WASM_BINARY,
 };
-use sc_service::ChainType;
+use sc_service::{ChainType, Properties};
+use serde::{Deserialize, Serialize};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{crypto::ByteArray, ed25519, sr25519, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::path::Path;
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
+/// Initial balance of every pre-funded account.
+const ENDOWMENT: Balance = 1 << 60;
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
-pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
+pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig, ChainSpecExtension>;
+
+/// Node-side consensus settings carried in the chain spec, next to the genesis state.
+///
+/// Every field may be left out of a chain spec file, in which case the default is used.
+#[derive(
+	Debug, Clone, PartialEq, Default, Serialize, Deserialize, sc_chain_spec::ChainSpecExtension,
+)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ChainSpecExtension {
+	/// GRANDPA voter settings.
+	pub grandpa: GrandpaSettings,
+	/// Aura block authoring settings.
+	pub aura: AuraSettings,
+	/// Slow down block authoring while finality lags behind. Authors never back off when `None`.
+	pub backoff_authoring: Option<BackoffAuthoringSettings>,
+}
+
+/// GRANDPA voter settings of a [`ChainSpecExtension`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct GrandpaSettings {
+	/// How often votes are gossiped, in milliseconds.
+	pub gossip_duration_ms: u64,
+	/// A justification is stored for every block whose number is a multiple of this period.
+	pub justification_period: u32,
+}
+
+impl Default for GrandpaSettings {
+	fn default() -> Self {
+		Self { gossip_duration_ms: 333, justification_period: 512 }
+	}
+}
+
+/// Aura block authoring settings of a [`ChainSpecExtension`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct AuraSettings {
+	/// Share of a slot an author may spend building its block.
+	pub block_proposal_slot_portion: f32,
+	/// Share of a slot an author may spend building its block after missed slots, when Aura
+	/// lengthens the proposal time. Aura picks a bound itself when `None`.
+	pub max_block_proposal_slot_portion: Option<f32>,
+}
+
+impl Default for AuraSettings {
+	fn default() -> Self {
+		Self { block_proposal_slot_portion: 2f32 / 3f32, max_block_proposal_slot_portion: None }
+	}
+}
+
+/// Settings of `BackoffAuthoringOnFinalizedHeadLagging` in a [`ChainSpecExtension`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct BackoffAuthoringSettings {
+	/// Upper bound of the number of slots skipped between two authored blocks.
+	pub max_interval: BlockNumber,
+	/// Number of unfinalized blocks tolerated before authors start to back off.
+	pub unfinalized_slack: BlockNumber,
+	/// The higher this is, the more blocks are authored while finality lags.
+	pub authoring_bias: BlockNumber,
+}
+
+impl Default for BackoffAuthoringSettings {
+	fn default() -> Self {
+		Self { max_interval: 100, unfinalized_slack: 50, authoring_bias: 2 }
+	}
+}
+
+/// The chain spec properties that describe `token` to wallets.
+pub fn token_properties(token: &TokenMetadata) -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("tokenSymbol".into(), String::from_utf8_lossy(&token.symbol).into());
+	properties.insert("tokenDecimals".into(), token.decimals.into());
+	properties.insert("ss58Format".into(), token.ss58_format.into());
+	properties
+}
+
+/// Checks that the token `properties` of a chain spec describe the `token` of its runtime.
+///
+/// Properties that are left out are not checked.
+pub fn check_token_properties(
+	properties: &Properties,
+	token: &TokenMetadata,
+) -> Result<(), String> {
+	for (key, expected) in token_properties(token) {
+		if let Some(value) = properties.get(&key).filter(|value| **value != expected) {
+			return Err(format!(
+				"chain spec property `{}` is {}, but the runtime uses {}",
+				key, value, expected
+			))
+		}
+	}
+	Ok(())
+}
+
+/// Names whose `//<name>` and `//<name>//stash` keys are used by the development chains.
+const DEV_SEEDS: [&str; 6] = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+
+/// Whether `chain_type` is meant for development, so that it may use development keys.
+///
+/// `Local` counts as development, as the `local` chain spec runs `//Alice` and `//Bob` as its
+/// authorities. Only `Live` and `Custom` chains refuse development keys.
+pub fn is_dev_chain(chain_type: &ChainType) -> bool {
+	matches!(chain_type, ChainType::Development | ChainType::Local)
+}
+
+/// Whether `public` is one of the well-known development keys, such as the key of `//Alice`.
+pub fn is_dev_key(public: &[u8]) -> bool {
+	DEV_SEEDS
+		.iter()
+		.flat_map(|name| [name.to_string(), format!("{}//stash", name)])
+		.any(|seed| {
+			get_from_seed::<sr25519::Public>(&seed).as_slice() == public ||
+				get_from_seed::<ed25519::Public>(&seed).as_slice() == public
+		})
+}
+
+/// Fails if a chain of type `chain_type` that is not a development chain has an authority with a
+/// well-known development key, as anyone could author blocks with it.
+pub fn check_authority_keys(
+	chain_type: &ChainType,
+	aura: &[AuraId],
+	grandpa: &[GrandpaId],
+) -> Result<(), String> {
+	if !is_dev_chain(chain_type) &&
+		(aura.iter().any(|key| is_dev_key(key.as_ref())) ||
+			grandpa.iter().any(|key| is_dev_key(key.as_ref())))
+	{
+		return Err(format!(
+			"the authorities use well-known development keys, which cannot be used on a {:?} chain",
+			chain_type
+		))
+	}
+	Ok(())
+}
 
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
@@ -31,12 +174,26 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
-	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate a validator account together with its Aura and Grandpa session keys.
+pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
+	(
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+	)
 }
 
-pub fn development_config() -> Result<ChainSpec, String> {
+fn session_keys(aura: AuraId, grandpa: GrandpaId) -> SessionKeys {
+	SessionKeys { aura, grandpa }
+}
+
+/// Gives every account in `accounts` the same `ENDOWMENT`.
+fn endowed(accounts: Vec<AccountId>) -> Vec<(AccountId, Balance)> {
+	accounts.into_iter().map(|k| (k, ENDOWMENT)).collect()
+}
+
+/// `initial_something` seeds `TemplateModule::Something` for every pre-funded account.
+pub fn development_config(initial_something: Option<u32>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
@@ -50,15 +207,19 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				wasm_binary,
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				// Council members
+				vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
 				// Pre-funded accounts
-				vec![
+				endowed(vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
 					get_account_id_from_seed::<sr25519::Public>("Bob"),
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-				],
+				]),
+				// Vesting schedules
+				vec![],
+				// Initial `TemplateModule::Something` value
+				initial_something,
 				true,
 			)
 		},
@@ -70,13 +231,14 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		None,
 		None,
 		// Properties
-		None,
+		Some(token_properties(&token_metadata())),
 		// Extensions
-		None,
+		ChainSpecExtension::default(),
 	))
 }
 
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
+/// `initial_something` seeds `TemplateModule::Something` for every pre-funded account.
+pub fn local_testnet_config(initial_something: Option<u32>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
@@ -90,13 +252,17 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				wasm_binary,
 				// Initial PoA authorities
 				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
-				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				// Pre-funded accounts
+				// Council members
 				vec![
 					get_account_id_from_seed::<sr25519::Public>("Alice"),
 					get_account_id_from_seed::<sr25519::Public>("Bob"),
 					get_account_id_from_seed::<sr25519::Public>("Charlie"),
+				],
+				// Pre-funded accounts
+				endowed(vec![
+					get_account_id_from_seed::<sr25519::Public>("Alice"),
+					get_account_id_from_seed::<sr25519::Public>("Bob"),
+					get_account_id_from_seed::<sr25519::Public>("Charlie"),
 					get_account_id_from_seed::<sr25519::Public>("Dave"),
 					get_account_id_from_seed::<sr25519::Public>("Eve"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
@@ -106,7 +272,18 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
+				]),
+				// Vesting schedules: the stash accounts unlock 90% of their funds over a year,
+				// starting after a month.
+				["Alice//stash", "Bob//stash", "Charlie//stash"]
+					.into_iter()
+					.map(|seed| {
+						let who = get_account_id_from_seed::<sr25519::Public>(seed);
+						(who, 30 * DAYS, 365 * DAYS, ENDOWMENT / 10)
+					})
+					.collect(),
+				// Initial `TemplateModule::Something` value
+				initial_something,
 				true,
 			)
 		},
@@ -116,20 +293,48 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		None,
 		// Protocol ID
 		None,
-		// Properties
-		None,
 		None,
+		// Properties
+		Some(token_properties(&token_metadata())),
 		// Extensions
-		None,
+		ChainSpecExtension {
+			backoff_authoring: Some(BackoffAuthoringSettings::default()),
+			..Default::default()
+		},
 	))
 }
 
+/// The `staging` network, as described in `res/staging.json`.
+///
+/// `authorities_from` replaces the authorities of the description with the ones in that file.
+pub fn staging_config(authorities_from: Option<&Path>) -> Result<ChainSpec, String> {
+	preset(include_str!("../res/staging.json"), authorities_from)
+}
+
+/// The `production` network, as described in `res/production.json`.
+///
+/// `authorities_from` replaces the authorities of the description with the ones in that file.
+pub fn production_config(authorities_from: Option<&Path>) -> Result<ChainSpec, String> {
+	preset(include_str!("../res/production.json"), authorities_from)
+}
+
+fn preset(description: &str, authorities_from: Option<&Path>) -> Result<ChainSpec, String> {
+	let mut description: GenesisDescription = serde_json::from_str(description)
+		.map_err(|e| format!("embedded genesis description is invalid: {}", e))?;
+	if let Some(path) = authorities_from {
+		description.authorities = read_authorities(path)?;
+	}
+	description.build()
+}
+
 /// Configure initial storage state for FRAME modules.
-fn testnet_genesis(
+pub fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(AuraId, GrandpaId)>,
-	root_key: AccountId,
-	endowed_accounts: Vec<AccountId>,
+	initial_authorities: Vec<(AccountId, AuraId, GrandpaId)>,
+	council_members: Vec<AccountId>,
+	endowed_accounts: Vec<(AccountId, Balance)>,
+	vesting: Vec<(AccountId, BlockNumber, BlockNumber, Balance)>,
+	initial_something: Option<u32>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -138,19 +343,98 @@ fn testnet_genesis(
 			code: wasm_binary.to_vec(),
 		},
 		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			// Configure endowed accounts with their initial balance.
+			balances: endowed_accounts.clone(),
 		},
-		aura: AuraConfig {
-			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+		// Every vested account has to be pre-funded, as only its endowment can be locked. In each
+		// `(who, cliff, length, liquid)` schedule, all but `liquid` stays locked until the `cliff`
+		// block and then unlocks linearly over `length` blocks.
+		vesting: VestingConfig { vesting },
+		validator_set: ValidatorSetConfig {
+			initial_validators: initial_authorities.iter().map(|x| x.0.clone()).collect(),
 		},
-		grandpa: GrandpaConfig {
-			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+		session: SessionConfig {
+			keys: initial_authorities
+				.iter()
+				.map(|x| (x.0.clone(), x.0.clone(), session_keys(x.1.clone(), x.2.clone())))
+				.collect(),
 		},
-		sudo: SudoConfig {
+		// The consensus authorities are set by the session pallet.
+		aura: AuraConfig { authorities: vec![] },
+		grandpa: GrandpaConfig { authorities: vec![] },
+		transaction_payment: Default::default(),
+		assets: Default::default(),
+		// The collective is seeded through the membership pallet, which keeps both in sync.
+		council: Default::default(),
+		council_membership: CouncilMembershipConfig {
 			// Assign network admin rights.
-			key: Some(root_key),
+			members: council_members.try_into().expect("too many council members"),
+			phantom: Default::default(),
 		},
-		transaction_payment: Default::default(),
+		treasury: Default::default(),
+		template_module: TemplateModuleConfig {
+			something: initial_something
+				.map(|value| endowed_accounts.into_iter().map(|(k, _)| (k, value)).collect())
+				.unwrap_or_default(),
+			// The validators report the totals of the offchain worker with their `tmpl` keys.
+			reporters: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::crypto::Ss58Codec;
+
+	#[test]
+	fn local_chains_count_as_development() {
+		assert!(is_dev_chain(&ChainType::Development));
+		assert!(is_dev_chain(&ChainType::Local));
+		assert!(!is_dev_chain(&ChainType::Live));
+		assert!(!is_dev_chain(&ChainType::Custom("test".into())));
+	}
+
+	#[test]
+	fn dev_authority_keys_are_refused_on_other_chains() {
+		let (_, alice_aura, alice_grandpa) = authority_keys_from_seed("Alice");
+		let (_, aura, grandpa) = authority_keys_from_seed("Validator1");
+		let (_, _, stash_grandpa) = authority_keys_from_seed("Bob//stash");
+
+		for chain_type in [ChainType::Development, ChainType::Local] {
+			assert_eq!(check_authority_keys(&chain_type, &[alice_aura.clone()], &[]), Ok(()));
+		}
+		for chain_type in [ChainType::Live, ChainType::Custom("test".into())] {
+			assert_eq!(
+				check_authority_keys(&chain_type, &[aura.clone()], &[grandpa.clone()]),
+				Ok(())
+			);
+			assert!(check_authority_keys(&chain_type, &[alice_aura.clone()], &[]).is_err());
+			assert!(check_authority_keys(&chain_type, &[], &[alice_grandpa.clone()]).is_err());
+			assert!(check_authority_keys(
+				&chain_type,
+				&[aura.clone()],
+				&[grandpa.clone(), stash_grandpa.clone()]
+			)
+			.is_err());
+		}
+	}
+
+	#[test]
+	fn presets_refuse_dev_authorities_from_files() {
+		let path = std::env::temp_dir()
+			.join(format!("node-template-dev-authorities-{}.json", std::process::id()));
+		let aura = get_from_seed::<sr25519::Public>("Alice").to_ss58check();
+		let grandpa = get_from_seed::<ed25519::Public>("Alice").to_ss58check();
+		let authorities =
+			format!(r#"{{"authorities": [{{"aura": "{}", "grandpa": "{}"}}]}}"#, aura, grandpa);
+		std::fs::write(&path, authorities).unwrap();
+
+		let staging = staging_config(Some(&path));
+		let production = production_config(Some(&path));
+		std::fs::remove_file(&path).unwrap();
+		for result in [staging, production] {
+			assert!(result.err().unwrap().contains("well-known development keys"));
+		}
 	}
 }
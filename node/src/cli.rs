@@ -1,4 +1,7 @@
+use crate::chain_spec::BackoffAuthoringSettings;
+use node_template_runtime::BlockNumber;
 use sc_cli::RunCmd;
+use std::path::PathBuf;
 
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -7,6 +10,117 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	#[clap(flatten)]
+	pub backoff: BackoffAuthoringParams,
+
+	/// Seed `TemplateModule::Something` with this value for every pre-funded account of the
+	/// `dev` and `local` chain specs. Left empty when not given.
+	#[arg(long, global = true, value_name = "VALUE")]
+	pub template_something: Option<u32>,
+
+	/// Take the authorities of the `staging` and `production` chain specs from this file instead
+	/// of the ones built into the node. The file lists their public keys under `authorities`, in
+	/// JSON or TOML, like a `generate-spec` genesis description.
+	#[arg(long, global = true, value_name = "FILE")]
+	pub authorities_from: Option<PathBuf>,
+
+	/// Seal blocks on demand instead of running Aura and GRANDPA. `instant` seals a block for
+	/// every transaction, `manual` only when asked through the `engine_createBlock` RPC and a
+	/// number seals a block every that many milliseconds. Blocks are finalized right away, or
+	/// through `engine_finalizeBlock`. Only allowed for chains of the `Development` type.
+	#[arg(long, value_name = "instant|manual|MILLISECONDS")]
+	pub sealing: Option<Sealing>,
+}
+
+/// How a development node seals blocks when Aura and GRANDPA are disabled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks only through the `engine_createBlock` RPC.
+	Manual,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl std::str::FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"instant" => Self::Instant,
+			"manual" => Self::Manual,
+			millis => match millis.parse() {
+				Ok(millis) if millis > 0 => Self::Interval(millis),
+				_ =>
+					return Err(format!(
+						"expected `instant`, `manual` or a positive number of milliseconds, got `{}`",
+						millis
+					)),
+			},
+		})
+	}
+}
+
+/// Overrides of the authoring back-off policy of the chain spec.
+///
+/// Setting any of the values turns back-off on, starting from the chain spec's policy, or from
+/// the defaults if it has none.
+#[derive(Debug, Clone, clap::Args)]
+pub struct BackoffAuthoringParams {
+	/// Keep authoring blocks however far finality lags behind, whatever the chain spec says.
+	#[arg(
+		long,
+		conflicts_with_all = [
+			"backoff_max_interval",
+			"backoff_unfinalized_slack",
+			"backoff_authoring_bias",
+		],
+	)]
+	pub no_backoff_authoring: bool,
+
+	/// Upper bound of the number of slots skipped between two authored blocks.
+	#[arg(long, value_name = "SLOTS")]
+	pub backoff_max_interval: Option<BlockNumber>,
+
+	/// Number of unfinalized blocks tolerated before the node starts to skip its slots.
+	#[arg(long, value_name = "BLOCKS")]
+	pub backoff_unfinalized_slack: Option<BlockNumber>,
+
+	/// The higher this is, the more blocks are authored while finality lags.
+	#[arg(long, value_name = "BIAS")]
+	pub backoff_authoring_bias: Option<BlockNumber>,
+}
+
+impl BackoffAuthoringParams {
+	/// Applies the overrides to the back-off `policy` of the chain spec.
+	pub fn apply(
+		&self,
+		policy: Option<BackoffAuthoringSettings>,
+	) -> Option<BackoffAuthoringSettings> {
+		if self.no_backoff_authoring {
+			return None
+		}
+		if self.backoff_max_interval.is_none() &&
+			self.backoff_unfinalized_slack.is_none() &&
+			self.backoff_authoring_bias.is_none()
+		{
+			return policy
+		}
+
+		let mut policy = policy.unwrap_or_default();
+		if let Some(max_interval) = self.backoff_max_interval {
+			policy.max_interval = max_interval;
+		}
+		if let Some(unfinalized_slack) = self.backoff_unfinalized_slack {
+			policy.unfinalized_slack = unfinalized_slack;
+		}
+		if let Some(authoring_bias) = self.backoff_authoring_bias {
+			policy.authoring_bias = authoring_bias;
+		}
+		Some(policy)
+	}
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -15,9 +129,16 @@ pub enum Subcommand {
 	#[command(subcommand)]
 	Key(sc_cli::KeySubcommand),
 
+	/// Provision the Aura and GRANDPA keys of an authority.
+	#[command(subcommand)]
+	Keys(crate::keys::KeysSubcommand),
+
 	/// Build a chain specification.
 	BuildSpec(sc_cli::BuildSpecCmd),
 
+	/// Generate a raw chain specification from a TOML or JSON genesis description.
+	GenerateSpec(crate::generate_spec::GenerateSpecCmd),
+
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
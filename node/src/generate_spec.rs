@@ -0,0 +1,509 @@
+//! The `generate-spec` subcommand, which turns a declarative genesis description into a raw
+//! chain spec.
+
+use crate::chain_spec::{self, ChainSpec, ChainSpecExtension};
+use node_template_runtime::{
+	impls::token_metadata, AccountId, Balance, BlockNumber, CouncilMaxMembers, MaxAuthorities,
+	TemplateMaxReporters, EXISTENTIAL_DEPOSIT, WASM_BINARY,
+};
+use sc_service::{config::MultiaddrWithPeerId, ChainType, Properties};
+use serde::{de::DeserializeOwned, Deserialize};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_core::{crypto::Ss58Codec, ed25519, sr25519};
+use sp_runtime::{
+	traits::{Get, IdentifyAccount},
+	MultiSigner,
+};
+use std::{
+	collections::BTreeSet,
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// Generate a raw chain spec from a genesis description.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct GenerateSpecCmd {
+	/// Genesis description to read. It is parsed as JSON if its name ends with `.json`, and as
+	/// TOML otherwise.
+	#[arg(value_name = "FILE")]
+	pub input: PathBuf,
+
+	/// Write the chain spec to this file instead of stdout.
+	#[arg(long, short, value_name = "FILE")]
+	pub output: Option<PathBuf>,
+}
+
+impl GenerateSpecCmd {
+	/// Runs the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let description: GenesisDescription = read_file(&self.input)?;
+		let json = description.build()?.as_json(true)?;
+		match &self.output {
+			Some(path) => fs::write(path, json)?,
+			None => println!("{}", json),
+		}
+		Ok(())
+	}
+}
+
+/// Reads `path` as JSON if its name ends with `.json`, and as TOML otherwise.
+fn read_file<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+	let input =
+		fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+	if path.extension().map_or(false, |ext| ext == "json") {
+		serde_json::from_str(&input).map_err(|e| format!("{}: {}", path.display(), e))
+	} else {
+		toml::from_str(&input).map_err(|e| format!("{}: {}", path.display(), e))
+	}
+}
+
+/// The authorities file read by `--authorities-from`, with the same `authorities` as a
+/// [`GenesisDescription`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AuthoritiesFile {
+	authorities: Vec<AuthorityDescription>,
+}
+
+/// Reads the public keys of the authorities from `path`, in JSON or TOML as `read_file` does.
+pub fn read_authorities(path: &Path) -> Result<Vec<AuthorityDescription>, String> {
+	read_file::<AuthoritiesFile>(path).map(|file| file.authorities)
+}
+
+/// The genesis of a network, as read by `generate-spec`.
+///
+/// Accounts and keys are given either in SS58 or as `0x`-prefixed hex public keys.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenesisDescription {
+	/// Name of the chain.
+	pub name: String,
+	/// Identifier of the chain, also used for its directory under the base path.
+	pub id: String,
+	/// `Live` unless given.
+	#[serde(default = "live")]
+	pub chain_type: ChainType,
+	/// Prefix of the network protocol names, so that nodes of different chains do not connect.
+	#[serde(default)]
+	pub protocol_id: Option<String>,
+	/// Boot nodes, as multiaddresses ending in `/p2p/<peer id>`.
+	#[serde(default)]
+	pub boot_nodes: Vec<String>,
+	/// Chain properties for user interfaces. `tokenSymbol`, `tokenDecimals` and `ss58Format`
+	/// have to match the runtime, and are taken from it unless given.
+	#[serde(default)]
+	pub properties: Properties,
+	/// The initial validators.
+	pub authorities: Vec<AuthorityDescription>,
+	/// The initial council members, who hold governance of the chain.
+	pub council: Vec<String>,
+	/// Initial free balances.
+	#[serde(default)]
+	pub balances: Vec<BalanceDescription>,
+	/// Initial vesting schedules of pre-funded accounts.
+	#[serde(default)]
+	pub vesting: Vec<VestingDescription>,
+}
+
+/// A validator of a [`GenesisDescription`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AuthorityDescription {
+	/// The validator account. The account of the Aura key unless given.
+	#[serde(default)]
+	pub account: Option<String>,
+	/// The sr25519 Aura key.
+	pub aura: String,
+	/// The ed25519 GRANDPA key.
+	pub grandpa: String,
+}
+
+/// An initial balance of a [`GenesisDescription`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BalanceDescription {
+	/// The pre-funded account.
+	pub account: String,
+	/// Its free balance, at least the existential deposit.
+	pub amount: Amount,
+}
+
+/// A vesting schedule of a [`GenesisDescription`].
+///
+/// All but `liquid` of the balance of `account` stays locked until block `cliff`, and then unlocks
+/// linearly over `length` blocks.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct VestingDescription {
+	/// The vested account, which needs a balance.
+	pub account: String,
+	/// The block at which the balance starts to unlock.
+	pub cliff: BlockNumber,
+	/// Number of blocks over which the balance unlocks.
+	pub length: BlockNumber,
+	/// The part of the balance that is never locked, zero unless given.
+	#[serde(default)]
+	pub liquid: Amount,
+}
+
+/// A balance, given as a number or, for amounts that TOML and JSON integers cannot hold, as a
+/// decimal string.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Amount {
+	/// An amount that fits into a `u64`.
+	Number(u64),
+	/// Any amount, in decimal.
+	Text(String),
+}
+
+impl Default for Amount {
+	fn default() -> Self {
+		Self::Number(0)
+	}
+}
+
+impl Amount {
+	fn parse(&self) -> Result<Balance, String> {
+		match self {
+			Self::Number(amount) => Ok((*amount).into()),
+			Self::Text(amount) =>
+				amount.parse().map_err(|_| format!("`{}` is not a valid amount", amount)),
+		}
+	}
+}
+
+fn live() -> ChainType {
+	ChainType::Live
+}
+
+/// Parses an SS58 address or a `0x`-prefixed hex public key.
+fn parse_key<T>(what: &str, key: &str) -> Result<T, String>
+where
+	T: Ss58Codec + for<'a> TryFrom<&'a [u8]>,
+{
+	let parsed = match key.strip_prefix("0x") {
+		Some(hex) => sp_core::bytes::from_hex(hex)
+			.ok()
+			.and_then(|bytes| T::try_from(&bytes[..]).ok()),
+		None => T::from_ss58check(key).ok(),
+	};
+	parsed
+		.ok_or_else(|| format!("{} `{}` is neither a valid SS58 address nor public key", what, key))
+}
+
+impl GenesisDescription {
+	/// Checks the description and builds its chain spec.
+	///
+	/// Everything that would make the genesis build panic is reported as an error instead.
+	pub fn build(self) -> Result<ChainSpec, String> {
+		let wasm_binary = WASM_BINARY.ok_or_else(|| "Wasm binary not available".to_string())?;
+
+		if self.authorities.is_empty() {
+			return Err("at least one authority is needed".into())
+		}
+		// Every authority is a validator and a reporter of `TemplateModule`.
+		let max_authorities = MaxAuthorities::get().min(TemplateMaxReporters::get());
+		if self.authorities.len() > max_authorities as usize {
+			return Err(format!("there are more than {} authorities", max_authorities))
+		}
+		let mut authorities = Vec::new();
+		for authority in &self.authorities {
+			let aura = parse_key::<sr25519::Public>("Aura key", &authority.aura)?;
+			let grandpa = parse_key::<ed25519::Public>("GRANDPA key", &authority.grandpa)?;
+			let account = match &authority.account {
+				Some(account) => parse_key::<AccountId>("account", account)?,
+				None => MultiSigner::from(aura).into_account(),
+			};
+			authorities.push((account, AuraId::from(aura), GrandpaId::from(grandpa)));
+		}
+		ensure_unique("authority account", authorities.iter().map(|a| &a.0))?;
+		ensure_unique("Aura key", authorities.iter().map(|a| &a.1))?;
+		ensure_unique("GRANDPA key", authorities.iter().map(|a| &a.2))?;
+
+		let council = self
+			.council
+			.iter()
+			.map(|member| parse_key::<AccountId>("council member", member))
+			.collect::<Result<Vec<_>, _>>()?;
+		if council.is_empty() {
+			return Err("at least one council member is needed".into())
+		}
+		if council.len() > CouncilMaxMembers::get() as usize {
+			return Err(format!("the council has more than {} members", CouncilMaxMembers::get()))
+		}
+		ensure_unique("council member", council.iter())?;
+
+		let mut balances = Vec::new();
+		for balance in &self.balances {
+			let account = parse_key::<AccountId>("account", &balance.account)?;
+			let amount = balance.amount.parse()?;
+			if amount < EXISTENTIAL_DEPOSIT {
+				return Err(format!(
+					"the balance of {} is below the existential deposit of {}",
+					balance.account, EXISTENTIAL_DEPOSIT
+				))
+			}
+			balances.push((account, amount));
+		}
+		ensure_unique("balance of account", balances.iter().map(|b| &b.0))?;
+
+		let mut vesting = Vec::new();
+		for schedule in &self.vesting {
+			let account = parse_key::<AccountId>("account", &schedule.account)?;
+			let balance = balances
+				.iter()
+				.find(|(who, _)| *who == account)
+				.map(|(_, amount)| *amount)
+				.ok_or_else(|| format!("vested account {} has no balance", schedule.account))?;
+			let liquid = schedule.liquid.parse()?;
+			let locked = balance.saturating_sub(liquid);
+			if locked == 0 || locked < schedule.length.into() {
+				return Err(format!(
+					"vesting schedule of {} unlocks less than one unit per block",
+					schedule.account
+				))
+			}
+			vesting.push((account, schedule.cliff, schedule.length, liquid));
+		}
+		// The vesting pallet only locks the last genesis schedule of an account.
+		ensure_unique("vesting schedule of account", vesting.iter().map(|v| &v.0))?;
+
+		let token = token_metadata();
+		chain_spec::check_token_properties(&self.properties, &token)?;
+		let mut properties = self.properties;
+		for (key, value) in chain_spec::token_properties(&token) {
+			properties.entry(key).or_insert(value);
+		}
+
+		// Anyone can derive the secret keys of the development accounts.
+		if !chain_spec::is_dev_chain(&self.chain_type) {
+			let dev = |key: &[u8]| chain_spec::is_dev_key(key);
+			let dev_authority = authorities.iter().any(|(account, aura, grandpa)| {
+				dev(account.as_ref()) || dev(aura.as_ref()) || dev(grandpa.as_ref())
+			});
+			let mut accounts = council.iter().chain(balances.iter().map(|(account, _)| account));
+			if dev_authority || accounts.any(|account| dev(account.as_ref())) {
+				return Err(format!(
+					"well-known development keys cannot be used on a {:?} chain",
+					self.chain_type
+				))
+			}
+		}
+
+		let boot_nodes = self
+			.boot_nodes
+			.iter()
+			.map(|node| {
+				node.parse::<MultiaddrWithPeerId>()
+					.map_err(|e| format!("boot node `{}` is invalid: {}", node, e))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(ChainSpec::from_genesis(
+			&self.name,
+			&self.id,
+			self.chain_type,
+			move || {
+				chain_spec::testnet_genesis(
+					wasm_binary,
+					authorities.clone(),
+					council.clone(),
+					balances.clone(),
+					vesting.clone(),
+					None,
+					false,
+				)
+			},
+			boot_nodes,
+			None,
+			self.protocol_id.as_deref(),
+			None,
+			Some(properties),
+			ChainSpecExtension::default(),
+		))
+	}
+}
+
+fn ensure_unique<'a, T: Ord + std::fmt::Debug + 'a>(
+	what: &str,
+	items: impl Iterator<Item = &'a T>,
+) -> Result<(), String> {
+	let mut seen = BTreeSet::new();
+	for item in items {
+		if !seen.insert(item) {
+			return Err(format!("duplicate {} {:?}", what, item))
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chain_spec::get_from_seed;
+
+	fn account(seed: &str) -> String {
+		get_from_seed::<sr25519::Public>(seed).to_ss58check()
+	}
+
+	fn authority(seed: &str) -> AuthorityDescription {
+		AuthorityDescription {
+			account: None,
+			aura: get_from_seed::<sr25519::Public>(seed).to_ss58check(),
+			grandpa: get_from_seed::<ed25519::Public>(seed).to_ss58check(),
+		}
+	}
+
+	fn balance(seed: &str, amount: u64) -> BalanceDescription {
+		BalanceDescription { account: account(seed), amount: Amount::Number(amount) }
+	}
+
+	fn description() -> GenesisDescription {
+		GenesisDescription {
+			name: "Test".into(),
+			id: "test".into(),
+			chain_type: ChainType::Live,
+			protocol_id: None,
+			boot_nodes: vec![],
+			properties: Properties::new(),
+			authorities: vec![authority("Validator1")],
+			council: vec![account("Council1")],
+			balances: vec![balance("Council1", 1_000)],
+			vesting: vec![],
+		}
+	}
+
+	fn build(description: GenesisDescription) -> Result<(), String> {
+		description.build().map(|_| ())
+	}
+
+	#[test]
+	fn keys_are_read_as_ss58_or_hex() {
+		let aura = get_from_seed::<sr25519::Public>("Validator1");
+		let grandpa = get_from_seed::<ed25519::Public>("Validator1");
+		let mut genesis = description();
+		genesis.authorities = vec![AuthorityDescription {
+			account: Some(sp_core::bytes::to_hex(aura.as_ref(), false)),
+			aura: sp_core::bytes::to_hex(aura.as_ref(), false),
+			grandpa: grandpa.to_ss58check(),
+		}];
+		assert_eq!(build(genesis), Ok(()));
+
+		let mut genesis = description();
+		genesis.authorities[0].aura = "0x1234".into();
+		assert!(build(genesis).unwrap_err().contains("is neither a valid"));
+
+		let mut genesis = description();
+		genesis.council = vec!["5NotAnAddress".into()];
+		assert!(build(genesis).unwrap_err().contains("is neither a valid"));
+	}
+
+	#[test]
+	fn duplicate_authorities_are_rejected() {
+		let mut genesis = description();
+		genesis.authorities.push(authority("Validator1"));
+		assert!(build(genesis).unwrap_err().starts_with("duplicate authority account"));
+
+		let mut genesis = description();
+		genesis.authorities.push(AuthorityDescription {
+			account: Some(account("Validator2")),
+			..authority("Validator1")
+		});
+		assert!(build(genesis).unwrap_err().starts_with("duplicate Aura key"));
+
+		let mut genesis = description();
+		genesis.authorities = vec![];
+		assert_eq!(build(genesis), Err("at least one authority is needed".into()));
+	}
+
+	#[test]
+	fn number_of_authorities_is_bounded() {
+		let authorities = |count: u32| {
+			(0..count).map(|i| authority(&format!("Validator{}", i))).collect::<Vec<_>>()
+		};
+		let max = MaxAuthorities::get().min(TemplateMaxReporters::get());
+
+		let mut genesis = description();
+		genesis.authorities = authorities(max);
+		assert_eq!(build(genesis), Ok(()));
+
+		let mut genesis = description();
+		genesis.authorities = authorities(max + 1);
+		assert!(build(genesis).unwrap_err().contains("more than"));
+	}
+
+	#[test]
+	fn balances_below_the_existential_deposit_are_rejected() {
+		let ed = EXISTENTIAL_DEPOSIT as u64;
+		let mut genesis = description();
+		genesis.balances = vec![balance("Council1", ed)];
+		assert_eq!(build(genesis), Ok(()));
+
+		let mut genesis = description();
+		genesis.balances = vec![balance("Council1", ed - 1)];
+		assert!(build(genesis).unwrap_err().contains("below the existential deposit"));
+	}
+
+	#[test]
+	fn vesting_has_to_unlock_at_least_one_unit_per_block() {
+		let schedule = |length, liquid| VestingDescription {
+			account: account("Council1"),
+			cliff: 10,
+			length,
+			liquid: Amount::Number(liquid),
+		};
+
+		let mut genesis = description();
+		genesis.vesting = vec![schedule(1_000, 0)];
+		assert_eq!(build(genesis), Ok(()));
+
+		let mut genesis = description();
+		genesis.vesting = vec![schedule(1_001, 0)];
+		assert!(build(genesis).unwrap_err().contains("less than one unit per block"));
+
+		let mut genesis = description();
+		genesis.vesting = vec![schedule(900, 200)];
+		assert!(build(genesis).unwrap_err().contains("less than one unit per block"));
+
+		let mut genesis = description();
+		genesis.vesting = vec![schedule(1_000, 1_000)];
+		assert!(build(genesis).unwrap_err().contains("less than one unit per block"));
+	}
+
+	#[test]
+	fn council_size_is_bounded() {
+		let members =
+			|count: u32| (0..count).map(|i| account(&format!("Council{}", i))).collect::<Vec<_>>();
+
+		let mut genesis = description();
+		genesis.council = vec![];
+		assert_eq!(build(genesis), Err("at least one council member is needed".into()));
+
+		let mut genesis = description();
+		genesis.council = members(CouncilMaxMembers::get());
+		assert_eq!(build(genesis), Ok(()));
+
+		let mut genesis = description();
+		genesis.council = members(CouncilMaxMembers::get() + 1);
+		assert!(build(genesis).unwrap_err().contains("more than"));
+	}
+
+	#[test]
+	fn dev_keys_are_rejected_on_live_chains() {
+		let mut genesis = description();
+		genesis.authorities = vec![authority("Alice")];
+		assert!(build(genesis).unwrap_err().contains("well-known development keys"));
+
+		let mut genesis = description();
+		genesis.balances.push(balance("Bob//stash", 1_000));
+		assert!(build(genesis).unwrap_err().contains("well-known development keys"));
+
+		let mut genesis = description();
+		genesis.chain_type = ChainType::Local;
+		genesis.authorities = vec![authority("Alice")];
+		genesis.council = vec![account("Alice")];
+		assert_eq!(build(genesis), Ok(()));
+	}
+}
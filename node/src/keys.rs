@@ -0,0 +1,260 @@
+//! The `keys` subcommand, which provisions the session keys of an authority in the keystore.
+
+use crate::chain_spec::{is_dev_chain, is_dev_key};
+use codec::{Decode, Encode};
+use node_template_runtime::{
+	opaque::SessionKeys, pallet_session::QueuedKeys, pallet_template, AccountId, Runtime,
+	StorageValue,
+};
+use sc_cli::{utils, Error, KeystoreParams, SharedParams, SubstrateCli};
+use sc_keystore::LocalKeystore;
+use sc_service::{config::KeystoreConfig, BasePath};
+use sp_core::{
+	crypto::{key_types, ByteArray, Ss58Codec},
+	ed25519,
+	hexdisplay::HexDisplay,
+	sr25519, Pair,
+};
+use sp_keystore::Keystore;
+use sp_runtime::BuildStorage;
+
+/// Session key management.
+#[derive(Debug, clap::Subcommand)]
+pub enum KeysSubcommand {
+	/// Put the Aura, GRANDPA and template reporter keys of one secret into the keystore and print
+	/// their `SessionKeys`, checking them against the authorities of the chain spec.
+	Init(InitKeysCmd),
+}
+
+impl KeysSubcommand {
+	/// Runs the subcommand.
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		match self {
+			KeysSubcommand::Init(cmd) => cmd.run(cli),
+		}
+	}
+}
+
+/// The `keys init` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct InitKeysCmd {
+	/// The secret URI of the keys, such as a mnemonic, or a file containing it. A new mnemonic is
+	/// generated and printed when not given.
+	///
+	/// The Aura and template reporter keys are the sr25519 key and the GRANDPA key the ed25519 key
+	/// of this secret.
+	#[arg(long)]
+	pub suri: Option<String>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl InitKeysCmd {
+	/// Runs the command.
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		let suri = match &self.suri {
+			Some(suri) => utils::read_uri(Some(suri))?,
+			None => {
+				let (_, phrase, _) = sr25519::Pair::generate_with_phrase(None);
+				println!("Generated a new mnemonic, keep it safe:\n\n  {}\n", phrase);
+				phrase
+			},
+		};
+		let aura = sr25519::Pair::from_string(&suri, None)
+			.map_err(|e| format!("invalid secret for the Aura key: {:?}", e))?
+			.public();
+		let grandpa = ed25519::Pair::from_string(&suri, None)
+			.map_err(|e| format!("invalid secret for the GRANDPA key: {:?}", e))?
+			.public();
+		let session_keys = SessionKeys { aura: aura.into(), grandpa: grandpa.into() };
+
+		let base_path = self
+			.shared_params
+			.base_path()?
+			.unwrap_or_else(|| BasePath::from_project("", "", &C::executable_name()));
+		let chain_id = self.shared_params.chain_id(self.shared_params.is_dev());
+		let chain_spec = cli.load_spec(&chain_id)?;
+		if !is_dev_chain(&chain_spec.chain_type()) &&
+			(is_dev_key(aura.as_slice()) || is_dev_key(grandpa.as_slice()))
+		{
+			return Err(format!(
+				"{} is not a development chain, so its keys cannot be development keys",
+				chain_spec.name()
+			)
+			.into())
+		}
+
+		// Check before touching the keystore, so that a half-matching pair is not stored.
+		let authority = genesis_authority(chain_spec.as_ref(), &session_keys)?;
+
+		let config_dir = base_path.config_dir(chain_spec.id());
+		let keystore = match self.keystore_params.keystore_config(&config_dir)? {
+			KeystoreConfig::Path { path, password } => LocalKeystore::open(path, password)?,
+			KeystoreConfig::InMemory =>
+				return Err("keys have to be stored in a keystore on disk".into()),
+		};
+		keystore
+			.insert(key_types::AURA, &suri, aura.as_slice())
+			.map_err(|_| Error::KeystoreOperation)?;
+		keystore
+			.insert(key_types::GRANDPA, &suri, grandpa.as_slice())
+			.map_err(|_| Error::KeystoreOperation)?;
+		// The offchain worker of the template pallet signs its reports with this key.
+		keystore
+			.insert(pallet_template::KEY_TYPE, &suri, aura.as_slice())
+			.map_err(|_| Error::KeystoreOperation)?;
+
+		println!("Aura key (sr25519):     {}", aura.to_ss58check());
+		println!("GRANDPA key (ed25519):  {}", grandpa.to_ss58check());
+		println!("Reporter key (sr25519): {}", aura.to_ss58check());
+		println!("SessionKeys:            0x{}", HexDisplay::from(&session_keys.encode()));
+		match authority {
+			Some(account) => println!(
+				"The keys are the session keys of authority {} in the {} chain spec.",
+				account,
+				chain_spec.name()
+			),
+			None => println!(
+				"The keys are not among the authorities of the {} chain spec. Register them with \
+				`session.setKeys` from the validator account before it joins the validator set.",
+				chain_spec.name()
+			),
+		}
+		Ok(())
+	}
+}
+
+/// The genesis authority whose session keys are `keys`, if any.
+///
+/// Fails if `keys` share their Aura or GRANDPA key with an authority without being its keys, which
+/// happens when one of the keys was derived from another secret or with another scheme.
+fn genesis_authority(
+	chain_spec: &dyn sc_service::ChainSpec,
+	keys: &SessionKeys,
+) -> Result<Option<AccountId>, Error> {
+	let storage = chain_spec.as_storage_builder().build_storage()?;
+	let authorities = match storage.top.get(&QueuedKeys::<Runtime>::hashed_key()[..]) {
+		Some(encoded) => Vec::<(AccountId, SessionKeys)>::decode(&mut &encoded[..])
+			.map_err(|e| format!("cannot decode the session keys of the chain spec: {}", e))?,
+		None => Vec::new(),
+	};
+
+	for (account, authority_keys) in authorities {
+		if authority_keys == *keys {
+			return Ok(Some(account))
+		}
+		if authority_keys.aura == keys.aura || authority_keys.grandpa == keys.grandpa {
+			return Err(format!(
+				"only one of the keys belongs to authority {}, whose session keys are 0x{}",
+				account,
+				HexDisplay::from(&authority_keys.encode())
+			)
+			.into())
+		}
+	}
+	Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		chain_spec::{self, authority_keys_from_seed, get_from_seed, ChainSpec},
+		cli::Cli,
+	};
+	use clap::Parser;
+	use node_template_runtime::WASM_BINARY;
+	use sc_service::ChainType;
+	use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+	use sp_consensus_grandpa::AuthorityId as GrandpaId;
+	use std::{fs, path::PathBuf};
+
+	fn keys(aura: &str, grandpa: &str) -> SessionKeys {
+		SessionKeys {
+			aura: get_from_seed::<AuraId>(aura),
+			grandpa: get_from_seed::<GrandpaId>(grandpa),
+		}
+	}
+
+	fn temp_dir(name: &str) -> PathBuf {
+		let dir =
+			std::env::temp_dir().join(format!("node-template-{}-{}", name, std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		dir
+	}
+
+	#[test]
+	fn genesis_authority_matches_both_keys() {
+		let dev = chain_spec::development_config(None).unwrap();
+		let (alice, _, _) = authority_keys_from_seed("Alice");
+
+		assert_eq!(genesis_authority(&dev, &keys("Alice", "Alice")).unwrap(), Some(alice));
+		assert_eq!(genesis_authority(&dev, &keys("Validator1", "Validator1")).unwrap(), None);
+	}
+
+	#[test]
+	fn genesis_authority_rejects_half_matching_keys() {
+		let dev = chain_spec::development_config(None).unwrap();
+
+		for keys in [keys("Alice", "Validator1"), keys("Validator1", "Alice")] {
+			let error = genesis_authority(&dev, &keys).unwrap_err().to_string();
+			assert!(error.contains("only one of the keys belongs to authority"), "{}", error);
+		}
+	}
+
+	#[test]
+	fn half_matching_keys_are_not_stored() {
+		let dir = temp_dir("keys-init");
+		// The only authority has the Aura key of `//Alice`, but another GRANDPA key.
+		let (alice, aura, _) = authority_keys_from_seed("Alice");
+		let grandpa = get_from_seed::<GrandpaId>("Validator1");
+		let spec = ChainSpec::from_genesis(
+			"Mismatch",
+			"mismatch",
+			ChainType::Development,
+			move || {
+				chain_spec::testnet_genesis(
+					WASM_BINARY.unwrap(),
+					vec![(alice.clone(), aura.clone(), grandpa.clone())],
+					vec![alice.clone()],
+					vec![(alice.clone(), 1 << 60)],
+					vec![],
+					None,
+					false,
+				)
+			},
+			vec![],
+			None,
+			None,
+			None,
+			None,
+			Default::default(),
+		);
+		let spec_path = dir.join("spec.json");
+		fs::write(&spec_path, spec.as_json(false).unwrap()).unwrap();
+		let base_path = dir.join("base");
+
+		let cmd = InitKeysCmd::parse_from([
+			"init",
+			"--suri",
+			"//Alice",
+			"--chain",
+			spec_path.to_str().unwrap(),
+			"--base-path",
+			base_path.to_str().unwrap(),
+		]);
+		let error = cmd.run(&Cli::parse_from(["node-template"])).unwrap_err().to_string();
+		let keystore = base_path.join("chains").join("mismatch").join("keystore");
+		let stored = fs::read_dir(&keystore).map_or(0, |entries| entries.count());
+		fs::remove_dir_all(&dir).unwrap();
+
+		assert!(error.contains("only one of the keys belongs to authority"), "{}", error);
+		assert_eq!(stored, 0);
+	}
+}
@@ -1,3 +1,7 @@
+pub mod backoff;
 pub mod chain_spec;
+pub mod cli;
+pub mod generate_spec;
+pub mod keys;
 pub mod rpc;
 pub mod service;
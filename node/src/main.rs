@@ -1,12 +1,15 @@
 //! Substrate Node Template CLI library.
 #![warn(missing_docs)]
 
+mod backoff;
 mod chain_spec;
 #[macro_use]
 mod service;
 mod benchmarking;
 mod cli;
 mod command;
+mod generate_spec;
+mod keys;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
@@ -0,0 +1,100 @@
+//! RPC interface for quoting transaction fees in assets.
+//!
+//! Transactions may pay their fees in a sufficient asset through `ChargeAssetTxPayment`. The
+//! fee is converted from the native fee at the ratio of the asset's minimum balance to the
+//! existential deposit.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Decode;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use node_template_runtime::{
+	apis::AssetTxPaymentApi as AssetTxPaymentRuntimeApi, AssetId, Balance,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+/// Fee quotes in assets.
+#[rpc(client, server)]
+pub trait AssetTxPaymentApi<BlockHash> {
+	/// The fee, tip included, of the encoded extrinsic `encoded_xt` when paid in `asset_id`, at
+	/// block `at`, or at the best block if `at` is `None`.
+	///
+	/// Returns `None` if the asset does not exist or cannot pay fees.
+	#[method(name = "assetTxPayment_queryFeeInAsset")]
+	fn query_fee_in_asset(
+		&self,
+		encoded_xt: Bytes,
+		asset_id: AssetId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<NumberOrHex>>;
+}
+
+/// Provides RPC methods to quote transaction fees in assets.
+pub struct AssetTxPayment<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> AssetTxPayment<C, Block> {
+	/// Creates a new instance of the AssetTxPayment Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The transaction could not be decoded.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::DecodeError => 1,
+			Error::RuntimeError => 2,
+		}
+	}
+}
+
+fn call_error(error: Error, message: &str, data: Option<String>) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(error.into(), message, data)).into()
+}
+
+impl<C, Block> AssetTxPaymentApiServer<<Block as BlockT>::Hash> for AssetTxPayment<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AssetTxPaymentRuntimeApi<Block, AssetId, Balance>,
+{
+	fn query_fee_in_asset(
+		&self,
+		encoded_xt: Bytes,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let encoded_len = encoded_xt.len() as u32;
+		let uxt: <Block as BlockT>::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| {
+			call_error(Error::DecodeError, "Unable to decode the extrinsic.", Some(e.to_string()))
+		})?;
+
+		let fee = api.query_fee_in_asset(at_hash, uxt, encoded_len, asset_id).map_err(|e| {
+			call_error(Error::RuntimeError, "Unable to query the asset fee.", Some(e.to_string()))
+		})?;
+
+		Ok(fee.map(NumberOrHex::from))
+	}
+}
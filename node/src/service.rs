@@ -1,13 +1,27 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use node_template_runtime::{self, opaque::Block, RuntimeApi};
+use crate::{
+	backoff::MeteredBackoff,
+	chain_spec::{check_authority_keys, check_token_properties, ChainSpecExtension},
+	cli::{BackoffAuthoringParams, Sealing},
+};
+use futures::{channel::mpsc, StreamExt};
+use node_template_runtime::{self, apis::TokenApi, opaque::Block, Hash, RuntimeApi};
 use sc_client_api::BlockBackend;
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 use sc_consensus_grandpa::SharedVoterState;
+use sc_consensus_manual_seal::{
+	consensus::{aura::AuraConsensusDataProvider, timestamp::SlotTimestampProvider},
+	EngineCommand, ManualSealParams,
+};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncParams};
 use sc_telemetry::{Telemetry, TelemetryWorker};
-use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::{sr25519::AuthorityPair as AuraPair, AuraApi};
+use sp_consensus_grandpa::GrandpaApi;
 use std::{sync::Arc, time::Duration};
 
 // Our native executor instance.
@@ -35,8 +49,12 @@ pub(crate) type FullClient =
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 
+/// Builds the components shared by all node services.
+///
+/// With `sealing` set, blocks carry no Aura seal and are imported without verification.
 pub fn new_partial(
 	config: &Configuration,
+	sealing: Option<Sealing>,
 ) -> Result<
 	sc_service::PartialComponents<
 		FullClient,
@@ -100,6 +118,25 @@ pub fn new_partial(
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
 
+	if sealing.is_some() {
+		let import_queue = sc_consensus_manual_seal::import_queue(
+			Box::new(client.clone()),
+			&task_manager.spawn_essential_handle(),
+			config.prometheus_registry(),
+		);
+
+		return Ok(sc_service::PartialComponents {
+			client,
+			backend,
+			task_manager,
+			import_queue,
+			keystore_container,
+			select_chain,
+			transaction_pool,
+			other: (grandpa_block_import, grandpa_link, telemetry),
+		})
+	}
+
 	let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
 
 	let import_queue =
@@ -138,7 +175,14 @@ pub fn new_partial(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// With `sealing` set, blocks are sealed by `sc-consensus-manual-seal` instead of Aura and
+/// finalized without GRANDPA. `backoff` overrides the authoring back-off policy of the chain spec.
+pub fn new_full(
+	mut config: Configuration,
+	sealing: Option<Sealing>,
+	backoff: BackoffAuthoringParams,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -148,7 +192,42 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		select_chain,
 		transaction_pool,
 		other: (block_import, grandpa_link, mut telemetry),
-	} = new_partial(&config)?;
+	} = new_partial(&config, sealing)?;
+
+	let best_hash = client.info().best_hash;
+	let runtime_api = client.runtime_api();
+
+	// Wallets read the token properties from the chain spec, so they have to match the runtime.
+	// Runtimes from before `TokenApi`, such as the one of a chain synced from its old blocks, are
+	// not checked.
+	let has_token_api = runtime_api
+		.has_api::<dyn TokenApi<Block>>(best_hash)
+		.map_err(|e| ServiceError::Application(e.into()))?;
+	if has_token_api {
+		let token = runtime_api
+			.token_metadata(best_hash)
+			.map_err(|e| ServiceError::Application(e.into()))?;
+		check_token_properties(&config.chain_spec.properties(), &token)
+			.map_err(ServiceError::Other)?;
+	}
+
+	// Anyone can derive the secret keys of the development authorities and author blocks with
+	// them, so only development chains may use them.
+	let aura =
+		runtime_api.authorities(best_hash).map_err(|e| ServiceError::Application(e.into()))?;
+	let grandpa = runtime_api
+		.grandpa_authorities(best_hash)
+		.map_err(|e| ServiceError::Application(e.into()))?
+		.into_iter()
+		.map(|(key, _)| key)
+		.collect::<Vec<_>>();
+	check_authority_keys(&config.chain_spec.chain_type(), &aura, &grandpa)
+		.map_err(|e| ServiceError::Other(format!("{}: {}", config.chain_spec.name(), e)))?;
+
+	let extension =
+		sc_chain_spec::get_extension::<ChainSpecExtension>(config.chain_spec.extensions())
+			.cloned()
+			.unwrap_or_default();
 
 	let grandpa_protocol_name = sc_consensus_grandpa::protocol_standard_name(
 		&client.block_hash(0).ok().flatten().expect("Genesis block exists; qed"),
@@ -187,18 +266,29 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 
 	let role = config.role.clone();
 	let force_authoring = config.force_authoring;
-	let backoff_authoring_blocks: Option<()> = None;
 	let name = config.network.node_name.clone();
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	// Commands sent through the `engine_*` RPC methods when sealing manually.
+	let (command_sink, rpc_commands) = if sealing.is_some() {
+		let (sink, stream) = mpsc::channel::<EngineCommand<Hash>>(1024);
+		(Some(sink), Some(stream))
+	} else {
+		(None, None)
+	};
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
 
 		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
@@ -218,6 +308,81 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	if let (Some(sealing), Some(rpc_commands)) = (sealing, rpc_commands) {
+		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool.clone(),
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		// Every mode also follows the commands sent through the RPC.
+		let commands_stream = match sealing {
+			Sealing::Manual => rpc_commands.boxed(),
+			Sealing::Instant => futures::stream::select(
+				rpc_commands,
+				transaction_pool.import_notification_stream().map(|_| {
+					EngineCommand::SealNewBlock {
+						create_empty: false,
+						finalize: true,
+						parent_hash: None,
+						sender: None,
+					}
+				}),
+			)
+			.boxed(),
+			Sealing::Interval(millis) => futures::stream::select(
+				rpc_commands,
+				futures::stream::unfold((), move |()| async move {
+					futures_timer::Delay::new(Duration::from_millis(millis)).await;
+					let command = EngineCommand::SealNewBlock {
+						create_empty: true,
+						finalize: true,
+						parent_hash: None,
+						sender: None,
+					};
+					Some((command, ()))
+				}),
+			)
+			.boxed(),
+		};
+
+		let manual_seal = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env: proposer_factory,
+			client: client.clone(),
+			pool: transaction_pool,
+			commands_stream,
+			select_chain,
+			consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client.clone()))),
+			create_inherent_data_providers: move |_, ()| {
+				let client = client.clone();
+				async move {
+					// Moves the timestamp one slot ahead for every block, however fast blocks
+					// are sealed, so that the runtime sees a new Aura slot each time.
+					let timestamp =
+						SlotTimestampProvider::new_aura(client).map_err(|e| format!("{:?}", e))?;
+					let slot =
+						sp_consensus_aura::inherents::InherentDataProvider::new(timestamp.slot());
+
+					Ok((slot, timestamp))
+				}
+			},
+		});
+
+		// the manual seal task is considered essential, i.e. if it
+		// fails we take down the service with it.
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"manual-seal",
+			Some("block-authoring"),
+			manual_seal,
+		);
+
+		network_starter.start_network();
+		return Ok(task_manager)
+	}
+
 	if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -229,6 +394,11 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 
 		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
 
+		let backoff_authoring_blocks = backoff
+			.apply(extension.backoff_authoring)
+			.map(|settings| MeteredBackoff::new(settings, prometheus_registry.as_ref()))
+			.transpose()?;
+
 		let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
 			StartAuraParams {
 				slot_duration,
@@ -252,8 +422,13 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 				keystore: keystore_container.keystore(),
 				sync_oracle: sync_service.clone(),
 				justification_sync_link: sync_service.clone(),
-				block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
-				max_block_proposal_slot_portion: None,
+				block_proposal_slot_portion: SlotProportion::new(
+					extension.aura.block_proposal_slot_portion,
+				),
+				max_block_proposal_slot_portion: extension
+					.aura
+					.max_block_proposal_slot_portion
+					.map(SlotProportion::new),
 				telemetry: telemetry.as_ref().map(|x| x.handle()),
 				compatibility_mode: Default::default(),
 			},
@@ -272,9 +447,8 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		let keystore = if role.is_authority() { Some(keystore_container.keystore()) } else { None };
 
 		let grandpa_config = sc_consensus_grandpa::Config {
-			// FIXME #1578 make this available through chainspec
-			gossip_duration: Duration::from_millis(333),
-			justification_period: 512,
+			gossip_duration: Duration::from_millis(extension.grandpa.gossip_duration_ms),
+			justification_period: extension.grandpa.justification_period,
 			name: Some(name),
 			observer_enabled: false,
 			keystore,
@@ -0,0 +1,70 @@
+//! Benchmarking setup for pallet-call-filter
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as CallFilter;
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use sp_runtime::traits::One;
+use sp_std::vec;
+
+/// A name of the maximum length.
+fn longest_name<T: Config>() -> PalletNameOf<T> {
+	BoundedVec::truncate_from(vec![b'a'; T::MaxNameLen::get() as usize])
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn pause() -> Result<(), BenchmarkError> {
+		let origin =
+			T::PauseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let key = (longest_name::<T>(), Some(longest_name::<T>()));
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, key.0.clone(), key.1.clone());
+
+		assert!(Paused::<T>::contains_key(&key));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unpause() -> Result<(), BenchmarkError> {
+		let origin =
+			T::PauseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let key = (longest_name::<T>(), Some(longest_name::<T>()));
+		Paused::<T>::insert(&key, ());
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, key.0.clone(), key.1.clone());
+
+		assert!(!Paused::<T>::contains_key(&key));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn enter_safe_mode() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SafeModeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, T::MaxSafeModeDuration::get());
+
+		assert!(SafeModeUntil::<T>::exists());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn exit_safe_mode() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SafeModeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		SafeModeUntil::<T>::put(T::BlockNumber::one());
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin);
+
+		assert!(!SafeModeUntil::<T>::exists());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(CallFilter, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,236 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Call Filter Pallet
+//!
+//! An emergency brake for the runtime's `BaseCallFilter`.
+//!
+//! `PauseOrigin` can pause a whole pallet or a single call of it, both identified by the names
+//! they have in the metadata. `SafeModeOrigin` can put the chain into safe mode for a limited
+//! number of blocks, during which every call is rejected. Calls in `WhitelistedCalls` are never
+//! filtered, so inherents and governance keep working however the filter is set.
+//!
+//! The runtime enables the filter by using this pallet as `frame_system::Config::BaseCallFilter`.
+//! Like any base filter it does not apply to the root origin.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{CallMetadata, Contains, GetCallMetadata},
+};
+
+/// Name of a pallet, as given in `construct_runtime!`.
+pub type PalletNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+/// Name of a call, as given by the function name of the dispatchable.
+pub type CallNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Saturating, Zero};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Calls that are neither paused nor blocked by safe mode.
+		type WhitelistedCalls: Contains<<Self as frame_system::Config>::RuntimeCall>;
+		/// Origin allowed to pause and unpause calls.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Origin allowed to enter and exit safe mode.
+		type SafeModeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Longest safe mode period that can be entered at once, in blocks.
+		#[pallet::constant]
+		type MaxSafeModeDuration: Get<Self::BlockNumber>;
+		/// Maximum length of a pallet or call name.
+		#[pallet::constant]
+		type MaxNameLen: Get<u32>;
+		/// Type representing the weight of this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Paused pallets and calls. A key without a call name pauses the whole pallet.
+	#[pallet::storage]
+	pub type Paused<T: Config> =
+		StorageMap<_, Blake2_128Concat, (PalletNameOf<T>, Option<CallNameOf<T>>), ()>;
+
+	/// The block at which safe mode ends, if it is active.
+	#[pallet::storage]
+	#[pallet::getter(fn safe_mode_until)]
+	pub type SafeModeUntil<T: Config> = StorageValue<_, T::BlockNumber>;
+
+	/// Why safe mode ended.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum ExitReason {
+		/// The safe mode period ran out.
+		Timeout,
+		/// `SafeModeOrigin` ended it early.
+		Force,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A pallet, or one of its calls if `call_name` is set, was paused.
+		Paused { pallet_name: PalletNameOf<T>, call_name: Option<CallNameOf<T>> },
+		/// A paused pallet or call was unpaused.
+		Unpaused { pallet_name: PalletNameOf<T>, call_name: Option<CallNameOf<T>> },
+		/// Safe mode was entered and lasts until block `until`.
+		SafeModeEntered { until: T::BlockNumber },
+		/// Safe mode was exited.
+		SafeModeExited { reason: ExitReason },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The pallet or call is already paused.
+		AlreadyPaused,
+		/// The pallet or call is not paused.
+		NotPaused,
+		/// Safe mode is already active.
+		AlreadyInSafeMode,
+		/// Safe mode is not active.
+		NotInSafeMode,
+		/// The safe mode duration is zero or longer than `MaxSafeModeDuration`.
+		InvalidDuration,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			match <SafeModeUntil<T>>::get() {
+				Some(until) if n >= until => {
+					<SafeModeUntil<T>>::kill();
+					Self::deposit_event(Event::SafeModeExited { reason: ExitReason::Timeout });
+					T::DbWeight::get().reads_writes(1, 2)
+				},
+				_ => T::DbWeight::get().reads(1),
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause `call_name` of `pallet_name`, or the whole pallet if no call name is given.
+		///
+		/// The dispatch origin for this call must be `PauseOrigin`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(
+			origin: OriginFor<T>,
+			pallet_name: PalletNameOf<T>,
+			call_name: Option<CallNameOf<T>>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			let key = (pallet_name, call_name);
+			ensure!(!<Paused<T>>::contains_key(&key), Error::<T>::AlreadyPaused);
+			<Paused<T>>::insert(&key, ());
+
+			let (pallet_name, call_name) = key;
+			Self::deposit_event(Event::Paused { pallet_name, call_name });
+			Ok(())
+		}
+
+		/// Lift a pause set by `pause` with the same arguments.
+		///
+		/// The dispatch origin for this call must be `PauseOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(
+			origin: OriginFor<T>,
+			pallet_name: PalletNameOf<T>,
+			call_name: Option<CallNameOf<T>>,
+		) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+
+			let key = (pallet_name, call_name);
+			<Paused<T>>::take(&key).ok_or(Error::<T>::NotPaused)?;
+
+			let (pallet_name, call_name) = key;
+			Self::deposit_event(Event::Unpaused { pallet_name, call_name });
+			Ok(())
+		}
+
+		/// Reject every call that is not whitelisted for the next `duration` blocks.
+		///
+		/// The dispatch origin for this call must be `SafeModeOrigin`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::enter_safe_mode())]
+		pub fn enter_safe_mode(origin: OriginFor<T>, duration: T::BlockNumber) -> DispatchResult {
+			T::SafeModeOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				!duration.is_zero() && duration <= T::MaxSafeModeDuration::get(),
+				Error::<T>::InvalidDuration
+			);
+			ensure!(!<SafeModeUntil<T>>::exists(), Error::<T>::AlreadyInSafeMode);
+
+			let until = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			<SafeModeUntil<T>>::put(until);
+
+			Self::deposit_event(Event::SafeModeEntered { until });
+			Ok(())
+		}
+
+		/// End safe mode before its period runs out.
+		///
+		/// The dispatch origin for this call must be `SafeModeOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::exit_safe_mode())]
+		pub fn exit_safe_mode(origin: OriginFor<T>) -> DispatchResult {
+			T::SafeModeOrigin::ensure_origin(origin)?;
+
+			<SafeModeUntil<T>>::take().ok_or(Error::<T>::NotInSafeMode)?;
+
+			Self::deposit_event(Event::SafeModeExited { reason: ExitReason::Force });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether the pallet `pallet_name` or its call `call_name` is paused.
+	pub fn is_paused(pallet_name: &str, call_name: &str) -> bool {
+		// Names longer than `MaxNameLen` cannot have been paused.
+		let pallet_name = match PalletNameOf::<T>::try_from(pallet_name.as_bytes().to_vec()) {
+			Ok(pallet_name) => pallet_name,
+			Err(_) => return false,
+		};
+		if <Paused<T>>::contains_key((pallet_name.clone(), None::<CallNameOf<T>>)) {
+			return true
+		}
+		CallNameOf::<T>::try_from(call_name.as_bytes().to_vec())
+			.map_or(false, |call_name| <Paused<T>>::contains_key((pallet_name, Some(call_name))))
+	}
+}
+
+impl<T: Config> Contains<<T as frame_system::Config>::RuntimeCall> for Pallet<T>
+where
+	<T as frame_system::Config>::RuntimeCall: GetCallMetadata,
+{
+	fn contains(call: &<T as frame_system::Config>::RuntimeCall) -> bool {
+		if T::WhitelistedCalls::contains(call) {
+			return true
+		}
+		if <SafeModeUntil<T>>::exists() {
+			return false
+		}
+		let CallMetadata { pallet_name, function_name } = call.get_call_metadata();
+		!Self::is_paused(pallet_name, function_name)
+	}
+}
@@ -0,0 +1,88 @@
+use crate as pallet_call_filter;
+use frame_support::{
+	ord_parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Contains},
+};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		CallFilter: pallet_call_filter,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = CallFilter;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+ord_parameter_types! {
+	pub const Governance: u64 = 1;
+}
+
+/// Calls of the pallet itself stay available, so governance can always lift the filter.
+pub struct WhitelistedCalls;
+
+impl Contains<RuntimeCall> for WhitelistedCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::CallFilter(_))
+	}
+}
+
+/// Longest safe mode period in the mock runtime.
+pub const MAX_DURATION: u64 = 10;
+
+impl pallet_call_filter::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WhitelistedCalls = WhitelistedCalls;
+	type PauseOrigin = EnsureSignedBy<Governance, u64>;
+	type SafeModeOrigin = EnsureSignedBy<Governance, u64>;
+	type MaxSafeModeDuration = ConstU64<MAX_DURATION>;
+	type MaxNameLen = ConstU32<32>;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities =
+		frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	// Go past genesis block so events get deposited
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
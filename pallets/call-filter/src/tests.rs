@@ -0,0 +1,158 @@
+use crate::{mock::*, Error, Event, ExitReason, PalletNameOf};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::{traits::Dispatchable, DispatchError};
+
+fn name(name: &str) -> PalletNameOf<Test> {
+	name.as_bytes().to_vec().try_into().unwrap()
+}
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn remark_with_event() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![] })
+}
+
+#[test]
+fn pause_requires_pause_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CallFilter::pause(RuntimeOrigin::signed(2), name("System"), None),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			CallFilter::enter_safe_mode(RuntimeOrigin::signed(2), 1),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn paused_call_is_filtered_until_unpaused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CallFilter::pause(
+			RuntimeOrigin::signed(1),
+			name("System"),
+			Some(name("remark"))
+		));
+		System::assert_last_event(
+			Event::Paused { pallet_name: name("System"), call_name: Some(name("remark")) }.into(),
+		);
+
+		assert_noop!(
+			remark().dispatch(RuntimeOrigin::signed(2)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		// Other calls of the pallet are not affected.
+		assert_ok!(remark_with_event().dispatch(RuntimeOrigin::signed(2)));
+
+		assert_ok!(CallFilter::unpause(
+			RuntimeOrigin::signed(1),
+			name("System"),
+			Some(name("remark"))
+		));
+		System::assert_last_event(
+			Event::Unpaused { pallet_name: name("System"), call_name: Some(name("remark")) }.into(),
+		);
+		assert_ok!(remark().dispatch(RuntimeOrigin::signed(2)));
+	});
+}
+
+#[test]
+fn paused_pallet_filters_all_its_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CallFilter::pause(RuntimeOrigin::signed(1), name("System"), None));
+
+		assert_noop!(
+			remark().dispatch(RuntimeOrigin::signed(2)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		assert_noop!(
+			remark_with_event().dispatch(RuntimeOrigin::signed(2)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		// The base filter does not apply to root.
+		assert_ok!(remark().dispatch(RuntimeOrigin::root()));
+	});
+}
+
+#[test]
+fn pause_and_unpause_reject_no_ops() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CallFilter::unpause(RuntimeOrigin::signed(1), name("System"), None),
+			Error::<Test>::NotPaused
+		);
+		assert_ok!(CallFilter::pause(RuntimeOrigin::signed(1), name("System"), None));
+		assert_noop!(
+			CallFilter::pause(RuntimeOrigin::signed(1), name("System"), None),
+			Error::<Test>::AlreadyPaused
+		);
+		// Pausing the pallet is not the same as pausing each of its calls.
+		assert_noop!(
+			CallFilter::unpause(RuntimeOrigin::signed(1), name("System"), Some(name("remark"))),
+			Error::<Test>::NotPaused
+		);
+	});
+}
+
+#[test]
+fn safe_mode_only_allows_whitelisted_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CallFilter::enter_safe_mode(RuntimeOrigin::signed(1), 5));
+		System::assert_last_event(Event::SafeModeEntered { until: 6 }.into());
+		assert_eq!(CallFilter::safe_mode_until(), Some(6));
+
+		assert_noop!(
+			remark().dispatch(RuntimeOrigin::signed(2)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		let exit = RuntimeCall::CallFilter(crate::Call::exit_safe_mode {});
+		assert_ok!(exit.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::SafeModeExited { reason: ExitReason::Force }.into());
+
+		assert_ok!(remark().dispatch(RuntimeOrigin::signed(2)));
+	});
+}
+
+#[test]
+fn safe_mode_ends_after_its_duration() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CallFilter::enter_safe_mode(RuntimeOrigin::signed(1), 5));
+
+		System::set_block_number(5);
+		CallFilter::on_initialize(5);
+		assert_eq!(CallFilter::safe_mode_until(), Some(6));
+
+		System::set_block_number(6);
+		CallFilter::on_initialize(6);
+		System::assert_last_event(Event::SafeModeExited { reason: ExitReason::Timeout }.into());
+		assert_eq!(CallFilter::safe_mode_until(), None);
+		assert_ok!(remark().dispatch(RuntimeOrigin::signed(2)));
+	});
+}
+
+#[test]
+fn safe_mode_checks_duration_and_state() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CallFilter::exit_safe_mode(RuntimeOrigin::signed(1)),
+			Error::<Test>::NotInSafeMode
+		);
+		assert_noop!(
+			CallFilter::enter_safe_mode(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::InvalidDuration
+		);
+		assert_noop!(
+			CallFilter::enter_safe_mode(RuntimeOrigin::signed(1), MAX_DURATION + 1),
+			Error::<Test>::InvalidDuration
+		);
+
+		assert_ok!(CallFilter::enter_safe_mode(RuntimeOrigin::signed(1), MAX_DURATION));
+		assert_noop!(
+			CallFilter::enter_safe_mode(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::AlreadyInSafeMode
+		);
+	});
+}
@@ -0,0 +1,89 @@
+//! Weights for pallet_call_filter
+//!
+//! These weights are estimated from the storage accesses of each call and have not been measured.
+//! Regenerate this file with the benchmark CLI before relying on them in production:
+//!
+//! ```text
+//! ./target/release/node-template benchmark pallet --chain dev --pallet pallet_call_filter \
+//!     --extrinsic '*' --steps 50 --repeat 20 --output pallets/call-filter/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_call_filter.
+pub trait WeightInfo {
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+	fn enter_safe_mode() -> Weight;
+	fn exit_safe_mode() -> Weight;
+}
+
+/// Weights for pallet_call_filter using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: CallFilter Paused (r:1 w:1)
+	/// Proof: CallFilter Paused (max_values: None, max_size: Some(67), added: 2542, mode: MaxEncodedLen)
+	fn pause() -> Weight {
+		Weight::from_parts(13_000_000, 3532)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CallFilter Paused (r:1 w:1)
+	/// Proof: CallFilter Paused (max_values: None, max_size: Some(67), added: 2542, mode: MaxEncodedLen)
+	fn unpause() -> Weight {
+		Weight::from_parts(14_000_000, 3532)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CallFilter SafeModeUntil (r:1 w:1)
+	/// Proof: CallFilter SafeModeUntil (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn enter_safe_mode() -> Weight {
+		Weight::from_parts(10_000_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: CallFilter SafeModeUntil (r:1 w:1)
+	/// Proof: CallFilter SafeModeUntil (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn exit_safe_mode() -> Weight {
+		Weight::from_parts(10_000_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: CallFilter Paused (r:1 w:1)
+	/// Proof: CallFilter Paused (max_values: None, max_size: Some(67), added: 2542, mode: MaxEncodedLen)
+	fn pause() -> Weight {
+		Weight::from_parts(13_000_000, 3532)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CallFilter Paused (r:1 w:1)
+	/// Proof: CallFilter Paused (max_values: None, max_size: Some(67), added: 2542, mode: MaxEncodedLen)
+	fn unpause() -> Weight {
+		Weight::from_parts(14_000_000, 3532)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CallFilter SafeModeUntil (r:1 w:1)
+	/// Proof: CallFilter SafeModeUntil (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn enter_safe_mode() -> Weight {
+		Weight::from_parts(10_000_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: CallFilter SafeModeUntil (r:1 w:1)
+	/// Proof: CallFilter SafeModeUntil (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn exit_safe_mode() -> Weight {
+		Weight::from_parts(10_000_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -0,0 +1,182 @@
+//! RPC interface for the template pallet.
+//!
+//! Lets clients read `pallet_template` state at any block through the [`TemplateRuntimeApi`]
+//! runtime API instead of decoding raw storage keys.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_rpc_api::DenyUnsafe;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, NumberFor, One, SaturatedConversion};
+
+pub use pallet_template_runtime_api::TemplateApi as TemplateRuntimeApi;
+
+/// Maximum number of blocks a single `template_history` request may span.
+pub const MAX_HISTORY_BLOCKS: u64 = 1_000;
+
+#[rpc(client, server)]
+pub trait TemplateApi<BlockHash, BlockNumber, AccountId> {
+	/// The value stored by `who` at block `at`, or at the best block if `at` is `None`.
+	#[method(name = "template_getSomething")]
+	fn get_something(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<Option<u32>>;
+
+	/// The changes of the value stored by `who` between blocks `from` and `to` (inclusive).
+	///
+	/// Returns the value at `from`, followed by one entry for every block in which the value
+	/// changed. `to` defaults to the best block. At most `MAX_HISTORY_BLOCKS` blocks can be
+	/// requested at once.
+	///
+	/// This method is unsafe, since every block of the range costs a runtime call.
+	#[method(name = "template_history")]
+	fn history(
+		&self,
+		who: AccountId,
+		from: BlockNumber,
+		to: Option<BlockNumber>,
+	) -> RpcResult<Vec<(BlockNumber, Option<u32>)>>;
+
+	/// The value changes of all accounts still kept on chain at block `at`, or at the best block
+	/// if `at` is `None`, oldest first. The value is `None` where an entry was removed.
+	#[method(name = "template_recentHistory")]
+	fn recent_history(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(BlockNumber, AccountId, Option<u32>)>>;
+}
+
+/// Provides RPC methods to query the state of the template pallet.
+pub struct Template<C, Block> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Template<C, Block> {
+	/// Creates a new instance of the Template Rpc helper.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The requested block does not exist or the range is invalid.
+	InvalidRange,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidRange => 2,
+		}
+	}
+}
+
+fn call_error(error: Error, message: &str, data: Option<String>) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(error.into(), message, data)).into()
+}
+
+impl<C, Block, AccountId> TemplateApiServer<<Block as BlockT>::Hash, NumberFor<Block>, AccountId>
+	for Template<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: TemplateRuntimeApi<Block, AccountId, NumberFor<Block>>,
+	AccountId: Codec + Clone + Send + Sync + 'static,
+{
+	fn get_something(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<u32>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.get_something(at_hash, who).map_err(|e| {
+			call_error(
+				Error::RuntimeError,
+				"Unable to query the stored value.",
+				Some(e.to_string()),
+			)
+		})
+	}
+
+	fn history(
+		&self,
+		who: AccountId,
+		from: NumberFor<Block>,
+		to: Option<NumberFor<Block>>,
+	) -> RpcResult<Vec<(NumberFor<Block>, Option<u32>)>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let to = to.unwrap_or_else(|| self.client.info().best_number);
+		if from > to {
+			return Err(call_error(Error::InvalidRange, "`from` is after `to`.", None))
+		}
+		if (to - from).saturated_into::<u64>() >= MAX_HISTORY_BLOCKS {
+			return Err(call_error(
+				Error::InvalidRange,
+				"Requested range is too large.",
+				Some(format!("At most {} blocks can be requested at once.", MAX_HISTORY_BLOCKS)),
+			))
+		}
+
+		let mut changes: Vec<(NumberFor<Block>, Option<u32>)> = Vec::new();
+		let mut number = from;
+		loop {
+			let hash = self
+				.client
+				.hash(number)
+				.map_err(|e| {
+					call_error(Error::RuntimeError, "Unable to look up block.", Some(e.to_string()))
+				})?
+				.ok_or_else(|| {
+					call_error(Error::InvalidRange, "Unknown block.", Some(number.to_string()))
+				})?;
+			let value =
+				self.client.runtime_api().get_something(hash, who.clone()).map_err(|e| {
+					call_error(
+						Error::RuntimeError,
+						"Unable to query the stored value.",
+						Some(e.to_string()),
+					)
+				})?;
+			if changes.last().map_or(true, |(_, last)| *last != value) {
+				changes.push((number, value));
+			}
+
+			if number == to {
+				break
+			}
+			number += One::one();
+		}
+
+		Ok(changes)
+	}
+
+	fn recent_history(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(NumberFor<Block>, AccountId, Option<u32>)>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.recent_history(at_hash).map_err(|e| {
+			call_error(
+				Error::RuntimeError,
+				"Unable to query the value history.",
+				Some(e.to_string()),
+			)
+		})
+	}
+}
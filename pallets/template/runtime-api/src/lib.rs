@@ -0,0 +1,21 @@
+//! Runtime API definition for the template pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the state of `pallet_template`.
+	pub trait TemplateApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The value currently stored by `who`, if any.
+		fn get_something(who: AccountId) -> Option<u32>;
+
+		/// The most recent value changes kept on chain, oldest first, as
+		/// `(block number, account, value)`. The value is `None` where an entry was removed.
+		fn recent_history() -> Vec<(BlockNumber, AccountId, Option<u32>)>;
+	}
+}
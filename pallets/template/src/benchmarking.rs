@@ -5,7 +5,35 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Template;
 use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
+use frame_support::{
+	traits::{
+		fungible::{Inspect, Mutate},
+		EnsureOrigin,
+	},
+	BoundedVec,
+};
+use frame_system::{offchain::SignedPayload, RawOrigin};
+use sp_runtime::{
+	traits::{Saturating, StaticLookup, Zero},
+	RuntimeAppPublic,
+};
+use sp_std::vec::Vec;
+
+/// Give `who` enough funds to pay several storage deposits.
+fn fund<T: Config>(who: &T::AccountId) {
+	let amount = T::Currency::minimum_balance()
+		.saturating_add(T::SomethingDeposit::get().saturating_mul(10u32.into()));
+	T::Currency::set_balance(who, amount);
+}
+
+/// Fill `History` so that every benchmarked change has to drop its oldest entry.
+fn fill_history<T: Config>() {
+	let who: T::AccountId = account("history", 0, 0);
+	let entries = (0..T::MaxHistory::get())
+		.map(|i| (Zero::zero(), who.clone(), Some(i)))
+		.collect::<Vec<_>>();
+	History::<T>::put(BoundedVec::truncate_from(entries));
+}
 
 #[benchmarks]
 mod benchmarks {
@@ -15,20 +43,123 @@ mod benchmarks {
 	fn do_something() {
 		let value = 100u32.into();
 		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		fill_history::<T>();
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		do_something(RawOrigin::Signed(caller.clone()), value);
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert_eq!(Something::<T>::get(&caller).map(|info| info.value), Some(value));
 	}
 
 	#[benchmark]
 	fn cause_error() {
-		Something::<T>::put(100u32);
 		let caller: T::AccountId = whitelisted_caller();
+		Something::<T>::insert(&caller, SomethingInfo { value: 100, updated_at: Zero::zero() });
+		fill_history::<T>();
+		#[extrinsic_call]
+		cause_error(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(Something::<T>::get(&caller).map(|info| info.value), Some(101u32));
+	}
+
+	#[benchmark]
+	fn clear_something() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		Template::<T>::do_something(RawOrigin::Signed(caller.clone()).into(), 100u32)?;
+		fill_history::<T>();
+		#[extrinsic_call]
+		clear_something(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(Something::<T>::get(&caller), None);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_something() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let dest: T::AccountId = account("dest", 0, 0);
+		fund::<T>(&caller);
+		fund::<T>(&dest);
+		Template::<T>::do_something(RawOrigin::Signed(caller.clone()).into(), 100u32)?;
+		fill_history::<T>();
+		#[extrinsic_call]
+		transfer_something(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(dest.clone()));
+
+		assert_eq!(Something::<T>::get(&caller), None);
+		assert_eq!(Something::<T>::get(&dest).map(|info| info.value), Some(100u32));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_set() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("who", 0, 0);
+		fill_history::<T>();
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, T::Lookup::unlookup(who.clone()), 100u32);
+
+		assert_eq!(Something::<T>::get(&who).map(|info| info.value), Some(100u32));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_clear() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("who", 0, 0);
+		fund::<T>(&who);
+		Template::<T>::do_something(RawOrigin::Signed(who.clone()).into(), 100u32)?;
+		fill_history::<T>();
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, T::Lookup::unlookup(who.clone()));
+
+		assert_eq!(Something::<T>::get(&who), None);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn submit_total() {
+		let caller: T::AccountId = whitelisted_caller();
+		// The caller is the last reporter to be found.
+		let reporters = (1..T::MaxReporters::get())
+			.map(|i| account("reporter", i, 0))
+			.chain([caller.clone()])
+			.collect::<Vec<_>>();
+		Reporters::<T>::put(BoundedVec::truncate_from(reporters));
+		#[extrinsic_call]
+		submit_total(RawOrigin::Signed(caller), 100u64);
+
+		assert_eq!(OffchainTotal::<T>::get(), 100u64);
+	}
+
+	#[benchmark]
+	fn submit_total_unsigned() -> Result<(), BenchmarkError> {
+		let key = <RuntimeAppPublicOf<T> as RuntimeAppPublic>::generate_pair(None);
+		let public: T::Public = GenericPublicOf::<T>::from(key).into();
+		let payload = TotalPayload { block_number: Zero::zero(), total: 100u64, public };
+		let signature = payload.sign::<T::AuthorityId>().ok_or(BenchmarkError::Weightless)?;
+		#[extrinsic_call]
+		submit_total_unsigned(RawOrigin::None, payload, signature);
+
+		assert_eq!(OffchainTotal::<T>::get(), 100u64);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_reporters() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let reporters = (0..T::MaxReporters::get())
+			.map(|i| account("reporter", i, 0))
+			.collect::<Vec<T::AccountId>>();
+		let reporters = BoundedVec::truncate_from(reporters);
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		_(origin as T::RuntimeOrigin, reporters.clone());
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		assert_eq!(Reporters::<T>::get(), reporters);
+		Ok(())
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
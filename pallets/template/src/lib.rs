@@ -13,25 +13,158 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migrations;
 pub mod weights;
 pub use weights::*;
 
+use frame_support::traits::fungible;
+use sp_core::crypto::KeyTypeId;
+
+/// Balance type of the currency used for storage deposits.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as fungible::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Key type of the accounts the offchain worker signs its transactions with.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"tmpl");
+
+/// Local offchain storage key of the running total computed by the offchain worker.
+const OFFCHAIN_TOTAL_KEY: &[u8] = b"pallet-template::total";
+
+/// Crypto used by the offchain worker to sign transactions.
+///
+/// A key of type [`KEY_TYPE`] whose account is one of the `Reporters` has to be added to the
+/// node's keystore for the offchain worker to report totals.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Authority id used by runtimes whose accounts are `MultiSigner`s.
+	pub struct TemplateAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TemplateAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for TemplateAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{
+			fungible::{InspectHold, MutateHold},
+			tokens::{Fortitude, Precision, Restriction},
+		},
+	};
+	use frame_system::{
+		offchain::{
+			AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction,
+			SignedPayload, Signer, SigningTypes,
+		},
+		pallet_prelude::*,
+	};
+	use sp_runtime::{
+		offchain::storage::{MutateStorageError, StorageRetrievalError, StorageValueRef},
+		traits::{IdentifyAccount, SaturatedConversion, Saturating, StaticLookup, Zero},
+		RuntimeAppPublic,
+	};
+	use sp_std::vec::Vec;
+
+	type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+	pub(crate) type RuntimeAppPublicOf<T> = <<T as Config>::AuthorityId as AppCrypto<
+		<T as SigningTypes>::Public,
+		<T as SigningTypes>::Signature,
+	>>::RuntimeAppPublic;
+	pub(crate) type GenericPublicOf<T> = <<T as Config>::AuthorityId as AppCrypto<
+		<T as SigningTypes>::Public,
+		<T as SigningTypes>::Signature,
+	>>::GenericPublic;
+
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
-		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>
+			+ TryInto<Event<Self>>;
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
+		/// The currency in which storage deposits are held.
+		type Currency: fungible::Mutate<Self::AccountId> + MutateHold<Self::AccountId>;
+		/// The reason under which storage deposits are held on `Currency`.
+		#[pallet::constant]
+		type HoldReason: Get<<Self::Currency as InspectHold<Self::AccountId>>::Reason>;
+		/// The deposit held from an account for as long as it has a value stored. It is released
+		/// when the value is cleared.
+		#[pallet::constant]
+		type SomethingDeposit: Get<BalanceOf<Self>>;
+		/// Origin allowed to set or clear the value of any account.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Number of value changes kept in `History`. Once it is full, the oldest change is
+		/// dropped for every new one.
+		#[pallet::constant]
+		type MaxHistory: Get<u32>;
+		/// Maximum number of accounts in `Reporters`.
+		#[pallet::constant]
+		type MaxReporters: Get<u32>;
+		/// The identifier type for the offchain worker's signing keys.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// Minimum number of blocks between two unsigned transactions of the offchain worker.
+		#[pallet::constant]
+		type UnsignedInterval: Get<Self::BlockNumber>;
+		/// Priority of the unsigned transactions of the offchain worker.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+	}
+
+	/// A total computed by the offchain worker in `block_number`, signed by the key `public` of a
+	/// reporter.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct TotalPayload<Public, BlockNumber> {
+		/// The block in which the total was computed.
+		pub block_number: BlockNumber,
+		/// The running total.
+		pub total: u64,
+		/// The key of the reporter that signed the payload.
+		pub public: Public,
+	}
+
+	impl<T: SigningTypes> SignedPayload<T> for TotalPayload<T::Public, T::BlockNumber> {
+		fn public(&self) -> T::Public {
+			self.public.clone()
+		}
+	}
+
+	/// A value of the registry together with the block in which it was last written.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SomethingInfo<BlockNumber> {
+		/// The stored value.
+		pub value: u32,
+		/// The block in which `value` was last written.
+		pub updated_at: BlockNumber,
 	}
 
 	// The pallet's runtime storage items.
@@ -40,7 +173,69 @@ pub mod pallet {
 	#[pallet::getter(fn something)]
 	// Learn more about declaring storage items:
 	// https://docs.substrate.io/main-docs/build/runtime-storage/#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	/// The value stored by each account. Every signer owns its own entry, so accounts never
+	/// overwrite each other.
+	pub type Something<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, SomethingInfo<T::BlockNumber>>;
+
+	/// The most recent changes of `Something`, oldest first, as `(block number, account, value)`.
+	/// The value is `None` where the entry of the account was removed. Holds at most
+	/// `MaxHistory` entries.
+	#[pallet::storage]
+	#[pallet::getter(fn history)]
+	pub type History<T: Config> = StorageValue<
+		_,
+		BoundedVec<(T::BlockNumber, T::AccountId, Option<u32>), T::MaxHistory>,
+		ValueQuery,
+	>;
+
+	/// Sum of all values written to `Something`, as last reported by an offchain worker.
+	#[pallet::storage]
+	#[pallet::getter(fn offchain_total)]
+	pub type OffchainTotal<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The accounts allowed to report totals, as the accounts of their [`KEY_TYPE`] keys.
+	#[pallet::storage]
+	#[pallet::getter(fn reporters)]
+	pub type Reporters<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxReporters>, ValueQuery>;
+
+	/// The first block in which the offchain worker may send another unsigned transaction.
+	#[pallet::storage]
+	#[pallet::getter(fn next_unsigned_at)]
+	pub type NextUnsignedAt<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Initial `(account, value)` entries of the registry.
+		pub something: Vec<(T::AccountId, u32)>,
+		/// Initial `Reporters`.
+		pub reporters: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { something: Default::default(), reporters: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (who, something) in &self.something {
+				Pallet::<T>::hold_deposit(who)
+					.expect("genesis accounts must be able to afford the storage deposit");
+				<Something<T>>::insert(
+					who,
+					SomethingInfo { value: *something, updated_at: Zero::zero() },
+				);
+			}
+			let reporters = BoundedVec::try_from(self.reporters.clone())
+				.expect("genesis reporters must not exceed `MaxReporters`");
+			<Reporters<T>>::put(reporters);
+		}
+	}
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
@@ -50,6 +245,18 @@ pub mod pallet {
 		/// Event documentation should end with an array that provides descriptive names for event
 		/// parameters. [something, who]
 		SomethingStored { something: u32, who: T::AccountId },
+		/// An account removed its value from the registry. [who]
+		SomethingCleared { who: T::AccountId },
+		/// An account handed its value over to another account. [from, to, something]
+		SomethingTransferred { from: T::AccountId, to: T::AccountId, something: u32 },
+		/// The admin origin set the value of an account. [something, who]
+		SomethingForceSet { something: u32, who: T::AccountId },
+		/// The admin origin removed the value of an account. [who]
+		SomethingForceCleared { who: T::AccountId },
+		/// A reporter submitted a new total. [total, who]
+		TotalSubmitted { total: u64, who: T::AccountId },
+		/// The admin origin replaced the reporters. [reporters]
+		ReportersSet { reporters: Vec<T::AccountId> },
 	}
 
 	// Errors inform users that something went wrong.
@@ -59,6 +266,38 @@ pub mod pallet {
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// The destination account already has a value stored.
+		DestinationOccupied,
+		/// The signer is not one of the `Reporters`.
+		NotReporter,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Add the values written in this block to the running total kept in local offchain
+		/// storage and report the new total back to the chain.
+		///
+		/// Nothing is reported unless the keystore holds a [`KEY_TYPE`] key of one of the
+		/// `Reporters`. The total is sent in an unsigned transaction with a payload signed by that
+		/// key if `UnsignedInterval` allows it, and in a transaction signed by it otherwise.
+		fn offchain_worker(block_number: T::BlockNumber) {
+			let stored = Self::stored_in_block();
+			if stored.is_empty() {
+				return
+			}
+
+			let total = match Self::add_to_local_total(block_number, &stored) {
+				Ok(total) => total,
+				Err(e) => {
+					log::debug!(target: "runtime::template", "Skipping offchain worker: {}", e);
+					return
+				},
+			};
+
+			if let Err(e) = Self::submit_total(block_number, total) {
+				log::error!(target: "runtime::template", "Unable to submit total: {}", e);
+			}
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -67,7 +306,10 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
+		/// the signer's entry in storage and emits an event. This function must be dispatched by a
+		/// signed extrinsic.
+		///
+		/// The first value stored by an account holds `SomethingDeposit` from it.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::do_something())]
 		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
@@ -76,8 +318,13 @@ pub mod pallet {
 			// https://docs.substrate.io/main-docs/build/origins/
 			let who = ensure_signed(origin)?;
 
+			// Take the storage deposit when a new entry is created.
+			if !<Something<T>>::contains_key(&who) {
+				Self::hold_deposit(&who)?;
+			}
+
 			// Update storage.
-			<Something<T>>::put(something);
+			Self::store(&who, something);
 
 			// Emit an event.
 			Self::deposit_event(Event::SomethingStored { something, who });
@@ -89,20 +336,317 @@ pub mod pallet {
 		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::cause_error())]
 		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
+			let who = ensure_signed(origin)?;
 
-			// Read a value from storage.
-			match <Something<T>>::get() {
+			// Read the signer's value from storage.
+			match <Something<T>>::get(&who) {
 				// Return an error if the value has not been set.
 				None => return Err(Error::<T>::NoneValue.into()),
 				Some(old) => {
 					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+					let new = old.value.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
 					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
+					Self::store(&who, new);
+					Self::deposit_event(Event::SomethingStored { something: new, who });
 					Ok(())
 				},
 			}
 		}
+
+		/// Remove the signer's value from storage and release its storage deposit.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::clear_something())]
+		pub fn clear_something(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::remove(&who)?;
+			Self::release_deposit(&who)?;
+
+			Self::deposit_event(Event::SomethingCleared { who });
+			Ok(())
+		}
+
+		/// Move the signer's value to `dest`, leaving the signer without an entry.
+		///
+		/// The storage deposit moves along with the value and stays on hold for `dest`. Fails if
+		/// `dest` already has a value stored.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::transfer_something())]
+		pub fn transfer_something(
+			origin: OriginFor<T>,
+			dest: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+
+			ensure!(!<Something<T>>::contains_key(&dest), Error::<T>::DestinationOccupied);
+			let something = Self::remove(&who)?;
+			Self::transfer_deposit(&who, &dest)?;
+			Self::store(&dest, something);
+
+			Self::deposit_event(Event::SomethingTransferred { from: who, to: dest, something });
+			Ok(())
+		}
+
+		/// Set the value of `who`, overwriting any value it already has.
+		///
+		/// No storage deposit is taken for entries created this way. The dispatch origin for this
+		/// call must be `AdminOrigin`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::force_set())]
+		pub fn force_set(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+			something: u32,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			Self::store(&who, something);
+
+			Self::deposit_event(Event::SomethingForceSet { something, who });
+			Ok(())
+		}
+
+		/// Remove the value of `who` and release any storage deposit held for it.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::force_clear())]
+		pub fn force_clear(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			Self::remove(&who)?;
+			Self::release_deposit(&who)?;
+
+			Self::deposit_event(Event::SomethingForceCleared { who });
+			Ok(())
+		}
+
+		/// Report the running total computed by an offchain worker.
+		///
+		/// The dispatch origin for this call must be _Signed_ by one of the `Reporters`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::submit_total())]
+		pub fn submit_total(origin: OriginFor<T>, total: u64) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<Reporters<T>>::get().contains(&who), Error::<T>::NotReporter);
+
+			<OffchainTotal<T>>::put(total);
+
+			Self::deposit_event(Event::TotalSubmitted { total, who });
+			Ok(())
+		}
+
+		/// Report the running total in `payload`, signed by the [`KEY_TYPE`] key of one of the
+		/// `Reporters`.
+		///
+		/// The dispatch origin for this call must be _None_. Only one such transaction is
+		/// accepted every `UnsignedInterval` blocks, and only with a valid `signature`, see
+		/// [`Pallet::validate_unsigned`].
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::submit_total_unsigned())]
+		pub fn submit_total_unsigned(
+			origin: OriginFor<T>,
+			payload: TotalPayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			<OffchainTotal<T>>::put(payload.total);
+			let now = frame_system::Pallet::<T>::block_number();
+			<NextUnsignedAt<T>>::put(now.saturating_add(T::UnsignedInterval::get()));
+
+			let who = payload.public.into_account();
+			Self::deposit_event(Event::TotalSubmitted { total: payload.total, who });
+			Ok(())
+		}
+
+		/// Replace the accounts allowed to report totals.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::set_reporters())]
+		pub fn set_reporters(
+			origin: OriginFor<T>,
+			reporters: BoundedVec<T::AccountId, T::MaxReporters>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			<Reporters<T>>::put(&reporters);
+
+			Self::deposit_event(Event::ReportersSet { reporters: reporters.into_inner() });
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Accept an unsigned total only if it is signed by one of the `Reporters` and was
+		/// computed in a block in which the offchain worker was allowed to send one.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::submit_total_unsigned { payload, signature } = call else {
+				return InvalidTransaction::Call.into()
+			};
+
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+				return InvalidTransaction::BadProof.into()
+			}
+			if !<Reporters<T>>::get().contains(&payload.public.clone().into_account()) {
+				return InvalidTransaction::BadSigner.into()
+			}
+
+			let next_unsigned_at = <NextUnsignedAt<T>>::get();
+			if payload.block_number < next_unsigned_at {
+				return InvalidTransaction::Stale.into()
+			}
+			if payload.block_number > frame_system::Pallet::<T>::block_number() {
+				return InvalidTransaction::Future.into()
+			}
+
+			ValidTransaction::with_tag_prefix("TemplateOffchainWorker")
+				.priority(T::UnsignedPriority::get())
+				// Only one unsigned total is included per interval.
+				.and_provides(next_unsigned_at)
+				.longevity(T::UnsignedInterval::get().saturated_into::<u64>())
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Set the value of `who` and record the change in `History`.
+		fn store(who: &T::AccountId, something: u32) {
+			let now = frame_system::Pallet::<T>::block_number();
+			<Something<T>>::insert(who, SomethingInfo { value: something, updated_at: now });
+			Self::record(now, who, Some(something));
+		}
+
+		/// Remove the value of `who`, record the removal in `History` and return the removed
+		/// value.
+		fn remove(who: &T::AccountId) -> Result<u32, Error<T>> {
+			let info = <Something<T>>::take(who).ok_or(Error::<T>::NoneValue)?;
+			Self::record(frame_system::Pallet::<T>::block_number(), who, None);
+			Ok(info.value)
+		}
+
+		/// Append a change to `History`, dropping the oldest entry if it is full.
+		fn record(now: T::BlockNumber, who: &T::AccountId, something: Option<u32>) {
+			<History<T>>::mutate(|history| {
+				let len = history.len();
+				// Only fails if `MaxHistory` is zero, in which case nothing is kept.
+				let _ = history.force_insert_keep_right(len, (now, who.clone(), something));
+			});
+		}
+
+		/// The values written to `Something` in the current block, including the ones moved to
+		/// another account.
+		fn stored_in_block() -> Vec<u32> {
+			frame_system::Pallet::<T>::read_events_no_consensus()
+				.filter_map(|record| {
+					let event: <T as Config>::RuntimeEvent = record.event.into();
+					match event.try_into() {
+						Ok(Event::<T>::SomethingStored { something, .. }) |
+						Ok(Event::<T>::SomethingForceSet { something, .. }) |
+						Ok(Event::<T>::SomethingTransferred { something, .. }) => Some(something),
+						_ => None,
+					}
+				})
+				.collect()
+		}
+
+		/// Add `stored` to the total kept in local offchain storage and return the new total.
+		///
+		/// Every block is counted at most once, even if the offchain worker runs for it again.
+		fn add_to_local_total(
+			block_number: T::BlockNumber,
+			stored: &[u32],
+		) -> Result<u64, &'static str> {
+			let sum = stored.iter().fold(0u64, |sum, value| sum.saturating_add(*value as u64));
+			let local = StorageValueRef::persistent(OFFCHAIN_TOTAL_KEY);
+			let res = local.mutate(
+				|last: Result<Option<(T::BlockNumber, u64)>, StorageRetrievalError>| match last {
+					Ok(Some((counted, _))) if counted >= block_number =>
+						Err("block already counted"),
+					Ok(Some((_, total))) => Ok((block_number, total.saturating_add(sum))),
+					// Nothing counted yet, or the stored total cannot be decoded.
+					Ok(None) | Err(_) => Ok((block_number, sum)),
+				},
+			);
+
+			match res {
+				Ok((_, total)) => Ok(total),
+				Err(MutateStorageError::ValueFunctionFailed(e)) => Err(e),
+				Err(MutateStorageError::ConcurrentModification(_)) =>
+					Err("local total modified concurrently"),
+			}
+		}
+
+		/// Send `total` back to the chain with a local key of one of the `Reporters`.
+		fn submit_total(block_number: T::BlockNumber, total: u64) -> Result<(), &'static str> {
+			let reporters = <Reporters<T>>::get();
+			let keys = RuntimeAppPublicOf::<T>::all()
+				.into_iter()
+				.map(|key| GenericPublicOf::<T>::from(key).into())
+				.filter(|public: &T::Public| reporters.contains(&public.clone().into_account()))
+				.collect::<Vec<_>>();
+			if keys.is_empty() {
+				return Err("no key of a reporter in the keystore")
+			}
+			let signer = Signer::<T, T::AuthorityId>::any_account().with_filter(keys);
+
+			if <NextUnsignedAt<T>>::get() <= block_number {
+				let (_, res) = signer
+					.send_unsigned_transaction(
+						|account| TotalPayload {
+							block_number,
+							total,
+							public: account.public.clone(),
+						},
+						|payload, signature| Call::submit_total_unsigned { payload, signature },
+					)
+					.ok_or("no local account available")?;
+				return res.map_err(|()| "unable to submit unsigned transaction")
+			}
+
+			let (_, res) = signer
+				.send_signed_transaction(|_account| Call::submit_total { total })
+				.ok_or("no local account available")?;
+			res.map_err(|()| "unable to submit signed transaction")
+		}
+
+		/// Put `SomethingDeposit` on hold for `who`.
+		fn hold_deposit(who: &T::AccountId) -> DispatchResult {
+			T::Currency::hold(&T::HoldReason::get(), who, T::SomethingDeposit::get())
+		}
+
+		/// Release whatever storage deposit is held for `who`.
+		fn release_deposit(who: &T::AccountId) -> DispatchResult {
+			let reason = T::HoldReason::get();
+			let deposit = T::Currency::balance_on_hold(&reason, who);
+			T::Currency::release(&reason, who, deposit, Precision::BestEffort)?;
+			Ok(())
+		}
+
+		/// Move the storage deposit held for `from` onto `to`, keeping it on hold.
+		fn transfer_deposit(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+			let reason = T::HoldReason::get();
+			let deposit = T::Currency::balance_on_hold(&reason, from);
+			if !deposit.is_zero() {
+				T::Currency::transfer_on_hold(
+					&reason,
+					from,
+					to,
+					deposit,
+					Precision::Exact,
+					Restriction::OnHold,
+					Fortitude::Polite,
+				)?;
+			}
+			Ok(())
+		}
 	}
 }
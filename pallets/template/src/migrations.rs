@@ -0,0 +1,74 @@
+//! Storage migrations of the template pallet.
+//!
+//! Each migration checks the on-chain storage version before it touches any storage, so it can
+//! stay in the runtime's `Migrations` until every chain has been upgraded past it.
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	storage_alias,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Storage layout of version 0, the original template, in which `Something` was a single value
+/// owned by nobody.
+pub mod v0 {
+	use super::*;
+
+	/// `Something` as it was stored in version 0.
+	#[storage_alias]
+	pub type Something<T: Config> = StorageValue<Pallet<T>, u32>;
+}
+
+/// Migration to version 1, in which `Something` holds a [`SomethingInfo`] per account.
+pub mod v1 {
+	use super::*;
+
+	/// Removes the single `Something` value of version 0.
+	///
+	/// The value has no owner who could hold its deposit, so it cannot become an entry of the
+	/// map. Its key is the prefix of the map, where it would otherwise be left behind.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain = Pallet::<T>::on_chain_storage_version();
+			if on_chain != 0 {
+				log::info!(
+					target: "runtime::template",
+					"Skipping migration to v1, on-chain storage version is {:?}",
+					on_chain,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			if let Some(value) = v0::Something::<T>::take() {
+				log::info!(
+					target: "runtime::template",
+					"Removed the v0 value {} of `Something`",
+					value,
+				);
+			}
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(2, 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"storage version was not set to 1"
+			);
+			ensure!(!v0::Something::<T>::exists(), "the v0 value was not removed");
+			// Every entry has to decode in the new layout.
+			ensure!(
+				<Something<T>>::iter_keys().count() == <Something<T>>::iter_values().count(),
+				"some values cannot be decoded as `SomethingInfo`"
+			);
+			Ok(())
+		}
+	}
+}
@@ -1,12 +1,29 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
-use sp_core::H256;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use parking_lot::RwLock;
+use scale_info::TypeInfo;
+use sp_core::{
+	offchain::{
+		testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+	},
+	H256,
+};
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+	BuildStorage,
 };
+use std::sync::Arc;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+/// Extrinsic type the offchain worker submits to the test transaction pool.
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 // Configure a mock runtime to test the pallet.
@@ -17,6 +34,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		Balances: pallet_balances,
 		TemplateModule: pallet_template,
 	}
 );
@@ -39,7 +57,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -48,12 +66,118 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+#[derive(
+	Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, MaxEncodedLen, Debug, TypeInfo,
+)]
+pub enum HoldReason {
+	TemplateStorage,
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type HoldIdentifier = HoldReason;
+	type MaxHolds = ConstU32<1>;
+}
+
+/// The storage deposit held for every stored value.
+pub const DEPOSIT: u64 = 10;
+
+parameter_types! {
+	pub const TemplateHoldReason: HoldReason = HoldReason::TemplateStorage;
+}
+
 impl pallet_template::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type Currency = Balances;
+	type HoldReason = TemplateHoldReason;
+	type SomethingDeposit = ConstU64<DEPOSIT>;
+	type AdminOrigin = EnsureRoot<u64>;
+	type MaxHistory = ConstU32<3>;
+	type MaxReporters = ConstU32<2>;
+	type AuthorityId = TestAuthId;
+	type UnsignedInterval = ConstU64<UNSIGNED_INTERVAL>;
+	type UnsignedPriority = ConstU64<{ 1 << 20 }>;
 }
 
+/// Number of blocks between two unsigned transactions of the offchain worker.
+pub const UNSIGNED_INTERVAL: u64 = 5;
+
+/// Signs offchain worker transactions with the keys set through
+/// [`UintAuthorityId::set_all_keys`] instead of a keystore.
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+/// Balance given to accounts `1`, `2` and `3` at genesis.
+pub const ENDOWMENT: u64 = 100;
+
+/// The account allowed to report totals at genesis.
+pub const REPORTER: u64 = 5;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	// Unlike the genesis configs of single pallets, the one of the runtime also writes the storage
+	// versions of the pallets, as a new chain would.
+	GenesisConfig {
+		system: Default::default(),
+		balances: BalancesConfig { balances: vec![(1, ENDOWMENT), (2, ENDOWMENT), (3, ENDOWMENT)] },
+		template_module: TemplateModuleConfig { something: vec![], reporters: vec![REPORTER] },
+	}
+	.build_storage()
+	.unwrap()
+	.into()
+}
+
+/// Build genesis storage with offchain and transaction pool extensions registered, so that the
+/// offchain worker can run. Transactions it submits end up in the returned pool state.
+pub fn new_offchain_test_ext() -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>) {
+	let (offchain, _state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainDbExt::new(offchain.clone()));
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	(t, pool_state)
 }
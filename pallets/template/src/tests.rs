@@ -1,5 +1,29 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	self as pallet_template,
+	migrations::{v0, v1},
+	mock::*,
+	Error, Event, NextUnsignedAt, SomethingInfo, TotalPayload,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::InspectHold, GenesisBuild, Get, GetStorageVersion, Hooks, OnRuntimeUpgrade,
+		StorageVersion,
+	},
+	BoundedVec,
+};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::ValidateUnsigned,
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	DispatchError, TokenError,
+};
+
+/// The value stored for `who`, without the block it was written in.
+fn something(who: u64) -> Option<u32> {
+	TemplateModule::something(who).map(|info| info.value)
+}
 
 #[test]
 fn it_works_for_default_value() {
@@ -9,7 +33,7 @@ fn it_works_for_default_value() {
 		// Dispatch a signed extrinsic.
 		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
 		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_eq!(something(1), Some(42));
 		// Assert that the correct event was deposited
 		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
 	});
@@ -25,3 +49,561 @@ fn correct_error_for_none_value() {
 		);
 	});
 }
+
+#[test]
+fn values_are_kept_per_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(2), 7));
+		// Each signer only touches its own entry.
+		assert_eq!(something(1), Some(42));
+		assert_eq!(something(2), Some(7));
+
+		assert_ok!(TemplateModule::cause_error(RuntimeOrigin::signed(2)));
+		assert_eq!(something(1), Some(42));
+		assert_eq!(something(2), Some(8));
+	});
+}
+
+#[test]
+fn clear_something_removes_the_entry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::clear_something(RuntimeOrigin::signed(1)),
+			Error::<Test>::NoneValue
+		);
+
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::clear_something(RuntimeOrigin::signed(1)));
+		assert_eq!(something(1), None);
+		System::assert_last_event(Event::SomethingCleared { who: 1 }.into());
+	});
+}
+
+#[test]
+fn transfer_something_moves_the_value() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::transfer_something(RuntimeOrigin::signed(1), 2));
+
+		assert_eq!(something(1), None);
+		assert_eq!(something(2), Some(42));
+		System::assert_last_event(
+			Event::SomethingTransferred { from: 1, to: 2, something: 42 }.into(),
+		);
+	});
+}
+
+#[test]
+fn transfer_something_fails_for_occupied_destination() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::transfer_something(RuntimeOrigin::signed(1), 2),
+			Error::<Test>::NoneValue
+		);
+
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(2), 7));
+		assert_noop!(
+			TemplateModule::transfer_something(RuntimeOrigin::signed(1), 2),
+			Error::<Test>::DestinationOccupied
+		);
+	});
+}
+
+#[test]
+fn genesis_config_seeds_entries() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, ENDOWMENT), (2, ENDOWMENT)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_template::GenesisConfig::<Test> { something: vec![(1, 10), (2, 20)], reporters: vec![] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_eq!(something(1), Some(10));
+		assert_eq!(something(2), Some(20));
+		assert_eq!(something(3), None);
+		// Genesis entries pay the storage deposit like any other.
+		assert_eq!(held(1), DEPOSIT);
+		assert_eq!(held(2), DEPOSIT);
+	});
+}
+
+#[test]
+fn storing_a_value_holds_the_deposit_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_eq!(held(1), DEPOSIT);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT - DEPOSIT);
+
+		// Updating an existing entry does not take another deposit.
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 43));
+		assert_ok!(TemplateModule::cause_error(RuntimeOrigin::signed(1)));
+		assert_eq!(held(1), DEPOSIT);
+	});
+}
+
+#[test]
+fn storing_a_value_fails_without_funds_for_the_deposit() {
+	new_test_ext().execute_with(|| {
+		// Account 4 was never endowed.
+		assert_noop!(
+			TemplateModule::do_something(RuntimeOrigin::signed(4), 42),
+			TokenError::CannotCreateHold
+		);
+		assert_eq!(something(4), None);
+	});
+}
+
+#[test]
+fn clearing_a_value_releases_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::clear_something(RuntimeOrigin::signed(1)));
+
+		assert_eq!(held(1), 0);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT);
+	});
+}
+
+#[test]
+fn transferring_a_value_moves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::transfer_something(RuntimeOrigin::signed(1), 2));
+
+		assert_eq!(held(1), 0);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT - DEPOSIT);
+		assert_eq!(held(2), DEPOSIT);
+		assert_eq!(Balances::free_balance(2), ENDOWMENT);
+
+		// The new owner gets the deposit back when clearing the value.
+		assert_ok!(TemplateModule::clear_something(RuntimeOrigin::signed(2)));
+		assert_eq!(held(2), 0);
+		assert_eq!(Balances::free_balance(2), ENDOWMENT + DEPOSIT);
+	});
+}
+
+#[test]
+fn force_calls_require_admin_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::force_set(RuntimeOrigin::signed(1), 2, 42),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::force_clear(RuntimeOrigin::signed(1), 2),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn force_set_overwrites_without_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Accounts without funds can be given a value too.
+		assert_ok!(TemplateModule::force_set(RuntimeOrigin::root(), 4, 42));
+		assert_eq!(something(4), Some(42));
+		System::assert_last_event(Event::SomethingForceSet { something: 42, who: 4 }.into());
+
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 7));
+		assert_ok!(TemplateModule::force_set(RuntimeOrigin::root(), 1, 8));
+		assert_eq!(something(1), Some(8));
+		assert_eq!(held(1), DEPOSIT);
+	});
+}
+
+#[test]
+fn force_clear_removes_the_value_and_releases_the_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::force_clear(RuntimeOrigin::root(), 1),
+			Error::<Test>::NoneValue
+		);
+
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::force_clear(RuntimeOrigin::root(), 1));
+		assert_eq!(something(1), None);
+		assert_eq!(held(1), 0);
+		assert_eq!(Balances::free_balance(1), ENDOWMENT);
+		System::assert_last_event(Event::SomethingForceCleared { who: 1 }.into());
+	});
+}
+
+#[test]
+fn history_records_value_changes() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::cause_error(RuntimeOrigin::signed(1)));
+		assert_ok!(TemplateModule::transfer_something(RuntimeOrigin::signed(1), 2));
+
+		// The transfer removes the value of account 1. `MaxHistory` is 3 in the mock runtime, so
+		// the first write has been dropped.
+		assert_eq!(
+			TemplateModule::history().into_inner(),
+			vec![(2, 1, Some(43)), (2, 1, None), (2, 2, Some(43))]
+		);
+
+		// Failed calls leave the history untouched.
+		assert_noop!(
+			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn history_drops_the_oldest_change_when_full() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for value in 0..5 {
+			assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), value));
+		}
+		assert_ok!(TemplateModule::force_set(RuntimeOrigin::root(), 4, 7));
+
+		// `MaxHistory` is 3 in the mock runtime.
+		assert_eq!(
+			TemplateModule::history().into_inner(),
+			vec![(1, 1, Some(3)), (1, 1, Some(4)), (1, 4, Some(7))]
+		);
+	});
+}
+
+#[test]
+fn history_records_removed_values() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::clear_something(RuntimeOrigin::signed(1)));
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::force_set(RuntimeOrigin::root(), 2, 7));
+		assert_ok!(TemplateModule::force_clear(RuntimeOrigin::root(), 2));
+
+		assert_eq!(
+			TemplateModule::history().into_inner(),
+			vec![(1, 1, None), (2, 2, Some(7)), (2, 2, None)]
+		);
+	});
+}
+
+#[test]
+fn values_record_the_block_they_were_written_in() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_eq!(TemplateModule::something(1), Some(SomethingInfo { value: 42, updated_at: 1 }));
+
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::transfer_something(RuntimeOrigin::signed(1), 2));
+		assert_eq!(TemplateModule::something(2), Some(SomethingInfo { value: 42, updated_at: 2 }));
+	});
+}
+
+#[test]
+fn migration_to_v1_removes_the_single_value() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		StorageVersion::new(0).put::<TemplateModule>();
+		v0::Something::<Test>::put(10);
+
+		#[cfg(feature = "try-runtime")]
+		let state = v1::MigrateToV1::<Test>::pre_upgrade().unwrap();
+		v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		#[cfg(feature = "try-runtime")]
+		v1::MigrateToV1::<Test>::post_upgrade(state).unwrap();
+
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		assert!(!v0::Something::<Test>::exists());
+		// The values of the map share the prefix of the old value, but are kept.
+		assert_eq!(TemplateModule::something(1), Some(SomethingInfo { value: 42, updated_at: 1 }));
+	});
+}
+
+#[test]
+fn migration_to_v1_only_runs_on_v0() {
+	new_test_ext().execute_with(|| {
+		// Genesis already writes the current storage version.
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+
+		System::set_block_number(2);
+		let weight = v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(weight, <Test as frame_system::Config>::DbWeight::get().reads(1));
+		assert_eq!(TemplateModule::something(1), Some(SomethingInfo { value: 42, updated_at: 1 }));
+	});
+}
+
+#[test]
+fn offchain_worker_needs_a_key_of_a_reporter() {
+	let (mut t, pool_state) = new_offchain_test_ext();
+	t.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 10));
+
+		TemplateModule::offchain_worker(1);
+		assert!(pool_state.read().transactions.is_empty());
+
+		// Account 1 is not a reporter.
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 7));
+		UintAuthorityId::set_all_keys(vec![1u64]);
+		TemplateModule::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_sends_unsigned_total_with_signed_payload() {
+	let (mut t, pool_state) = new_offchain_test_ext();
+	t.execute_with(|| {
+		UintAuthorityId::set_all_keys(vec![1u64, REPORTER]);
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 10));
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(2), 5));
+
+		TemplateModule::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		let (payload, signature) = signed_total(REPORTER, 1, 15);
+		assert_eq!(
+			tx.call,
+			RuntimeCall::TemplateModule(pallet_template::Call::submit_total_unsigned {
+				payload,
+				signature,
+			})
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_sends_signed_total_within_the_interval() {
+	let (mut t, pool_state) = new_offchain_test_ext();
+	t.execute_with(|| {
+		UintAuthorityId::set_all_keys(vec![REPORTER]);
+		NextUnsignedAt::<Test>::put(1 + UNSIGNED_INTERVAL);
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 10));
+
+		TemplateModule::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, Some((0, ())));
+		assert_eq!(
+			tx.call,
+			RuntimeCall::TemplateModule(pallet_template::Call::submit_total { total: 10 })
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_keeps_a_running_total() {
+	let (mut t, pool_state) = new_offchain_test_ext();
+	t.execute_with(|| {
+		UintAuthorityId::set_all_keys(vec![REPORTER]);
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 10));
+		TemplateModule::offchain_worker(1);
+		// Running again for the same block neither counts nor submits anything.
+		TemplateModule::offchain_worker(1);
+		assert_eq!(pool_state.read().transactions.len(), 1);
+
+		System::reset_events();
+		System::set_block_number(2);
+		TemplateModule::offchain_worker(2);
+		// Nothing was stored in block 2.
+		assert_eq!(pool_state.read().transactions.len(), 1);
+
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 7));
+		TemplateModule::offchain_worker(2);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		let (payload, signature) = signed_total(REPORTER, 2, 17);
+		assert_eq!(
+			tx.call,
+			RuntimeCall::TemplateModule(pallet_template::Call::submit_total_unsigned {
+				payload,
+				signature,
+			})
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_counts_every_write() {
+	let (mut t, pool_state) = new_offchain_test_ext();
+	t.execute_with(|| {
+		UintAuthorityId::set_all_keys(vec![REPORTER]);
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 10));
+		assert_ok!(TemplateModule::cause_error(RuntimeOrigin::signed(1)));
+		assert_ok!(TemplateModule::force_set(RuntimeOrigin::root(), 4, 7));
+		assert_ok!(TemplateModule::transfer_something(RuntimeOrigin::signed(1), 2));
+		// Removals do not add anything.
+		assert_ok!(TemplateModule::clear_something(RuntimeOrigin::signed(2)));
+
+		TemplateModule::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		let (payload, signature) = signed_total(REPORTER, 1, 10 + 11 + 7 + 11);
+		assert_eq!(
+			tx.call,
+			RuntimeCall::TemplateModule(pallet_template::Call::submit_total_unsigned {
+				payload,
+				signature,
+			})
+		);
+	});
+}
+
+#[test]
+fn submit_total_requires_a_reporter() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::submit_total(RuntimeOrigin::none(), 5),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::submit_total(RuntimeOrigin::signed(1), 5),
+			Error::<Test>::NotReporter
+		);
+
+		assert_ok!(TemplateModule::submit_total(RuntimeOrigin::signed(REPORTER), 5));
+		assert_eq!(TemplateModule::offchain_total(), 5);
+		System::assert_last_event(Event::TotalSubmitted { total: 5, who: REPORTER }.into());
+	});
+}
+
+#[test]
+fn submit_total_unsigned_delays_the_next_unsigned_total() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (payload, signature) = signed_total(REPORTER, 1, 5);
+		assert_noop!(
+			TemplateModule::submit_total_unsigned(
+				RuntimeOrigin::signed(1),
+				payload.clone(),
+				signature.clone()
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(TemplateModule::submit_total_unsigned(
+			RuntimeOrigin::none(),
+			payload,
+			signature
+		));
+		assert_eq!(TemplateModule::offchain_total(), 5);
+		assert_eq!(TemplateModule::next_unsigned_at(), 1 + UNSIGNED_INTERVAL);
+		System::assert_last_event(Event::TotalSubmitted { total: 5, who: REPORTER }.into());
+	});
+}
+
+#[test]
+fn validate_unsigned_only_accepts_totals_after_the_interval() {
+	new_test_ext().execute_with(|| {
+		let validate = |block_number| {
+			let (payload, signature) = signed_total(REPORTER, block_number, 5);
+			let call = pallet_template::Call::submit_total_unsigned { payload, signature };
+			TemplateModule::validate_unsigned(TransactionSource::External, &call)
+		};
+
+		System::set_block_number(3);
+		assert!(validate(3).is_ok());
+		assert_eq!(validate(4), InvalidTransaction::Future.into());
+
+		let (payload, signature) = signed_total(REPORTER, 3, 5);
+		assert_ok!(TemplateModule::submit_total_unsigned(
+			RuntimeOrigin::none(),
+			payload,
+			signature
+		));
+		System::set_block_number(3 + UNSIGNED_INTERVAL);
+		assert_eq!(validate(3), InvalidTransaction::Stale.into());
+		assert!(validate(3 + UNSIGNED_INTERVAL).is_ok());
+
+		// Other calls cannot be submitted unsigned.
+		let call = pallet_template::Call::do_something { something: 5 };
+		assert_eq!(
+			TemplateModule::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Call.into()
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_only_accepts_totals_signed_by_a_reporter() {
+	new_test_ext().execute_with(|| {
+		let validate = |payload, signature| {
+			let call = pallet_template::Call::submit_total_unsigned { payload, signature };
+			TemplateModule::validate_unsigned(TransactionSource::External, &call)
+		};
+		System::set_block_number(1);
+
+		let (payload, signature) = signed_total(1, 1, 5);
+		assert_eq!(validate(payload, signature), InvalidTransaction::BadSigner.into());
+
+		// A payload signed by someone else than its key.
+		let (payload, _) = signed_total(REPORTER, 1, 5);
+		let (_, signature) = signed_total(1, 1, 5);
+		assert_eq!(validate(payload, signature), InvalidTransaction::BadProof.into());
+
+		// A signature of another payload.
+		let (mut payload, signature) = signed_total(REPORTER, 1, 5);
+		payload.total = 6;
+		assert_eq!(validate(payload, signature), InvalidTransaction::BadProof.into());
+	});
+}
+
+#[test]
+fn set_reporters_replaces_the_reporters() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let reporters = BoundedVec::truncate_from(vec![1, 2]);
+		assert_noop!(
+			TemplateModule::set_reporters(RuntimeOrigin::signed(REPORTER), reporters.clone()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(TemplateModule::set_reporters(RuntimeOrigin::root(), reporters));
+		assert_eq!(TemplateModule::reporters().into_inner(), vec![1, 2]);
+		System::assert_last_event(Event::ReportersSet { reporters: vec![1, 2] }.into());
+
+		assert_noop!(
+			TemplateModule::submit_total(RuntimeOrigin::signed(REPORTER), 5),
+			Error::<Test>::NotReporter
+		);
+		assert_ok!(TemplateModule::submit_total(RuntimeOrigin::signed(2), 5));
+	});
+}
+
+/// A payload reporting `total` in `block_number`, signed by the key `signer`.
+fn signed_total(
+	signer: u64,
+	block_number: u64,
+	total: u64,
+) -> (TotalPayload<UintAuthorityId, u64>, TestSignature) {
+	let payload = TotalPayload { block_number, total, public: UintAuthorityId(signer) };
+	let signature = TestSignature(signer, payload.encode());
+	(payload, signature)
+}
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::TemplateStorage, &who)
+}
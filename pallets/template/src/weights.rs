@@ -1,30 +1,12 @@
-
-//! Autogenerated weights for pallet_template
+//! Weights for pallet_template
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-04-06, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `Alexs-MacBook-Pro-2.local`, CPU: `<UNKNOWN>`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// ../../target/release/node-template
-// benchmark
-// pallet
-// --chain
-// dev
-// --pallet
-// pallet_template
-// --extrinsic
-// *
-// --steps=50
-// --repeat=20
-// --execution=wasm
-// --wasm-execution=compiled
-// --output
-// pallets/template/src/weights.rs
-// --template
-// ../../.maintain/frame-weight-template.hbs
+//! These weights are estimated from the storage accesses of each call and have not been measured.
+//! Regenerate this file with the benchmark CLI before relying on them in production:
+//!
+//! ```text
+//! ./target/release/node-template benchmark pallet --chain dev --pallet pallet_template \
+//!     --extrinsic '*' --steps 50 --repeat 20 --output pallets/template/src/weights.rs
+//! ```
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -37,55 +19,206 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn do_something() -> Weight;
 	fn cause_error() -> Weight;
+	fn clear_something() -> Weight;
+	fn transfer_something() -> Weight;
+	fn force_set() -> Weight;
+	fn force_clear() -> Weight;
+	fn submit_total() -> Weight;
+	fn submit_total_unsigned() -> Weight;
+	fn set_reporters() -> Weight;
 }
 
 /// Weights for pallet_template using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: TemplateModule Something (r:1 w:1)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		Weight::from_parts(30_000_000, 13177)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
 	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+		Weight::from_parts(14_000_000, 5487)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TemplateModule Something (r:1 w:1)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn clear_something() -> Weight {
+		Weight::from_parts(32_000_000, 15077)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TemplateModule Something (r:2 w:2)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:2 w:2)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn transfer_something() -> Weight {
+		Weight::from_parts(40_000_000, 18350)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: TemplateModule Something (r:0 w:1)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	fn force_set() -> Weight {
+		Weight::from_parts(14_000_000, 5487)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TemplateModule Something (r:1 w:1)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn force_clear() -> Weight {
+		Weight::from_parts(32_000_000, 15077)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: TemplateModule Reporters (r:1 w:0)
+	/// Proof: TemplateModule Reporters (max_values: Some(1), max_size: Some(1025), added: 1520, mode: MaxEncodedLen)
+	/// Storage: TemplateModule OffchainTotal (r:0 w:1)
+	/// Proof: TemplateModule OffchainTotal (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	fn submit_total() -> Weight {
+		Weight::from_parts(11_000_000, 2510)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: TemplateModule OffchainTotal (r:0 w:1)
+	/// Proof: TemplateModule OffchainTotal (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TemplateModule NextUnsignedAt (r:0 w:1)
+	/// Proof: TemplateModule NextUnsignedAt (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn submit_total_unsigned() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: TemplateModule Reporters (r:0 w:1)
+	/// Proof: TemplateModule Reporters (max_values: Some(1), max_size: Some(1025), added: 1520, mode: MaxEncodedLen)
+	fn set_reporters() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: TemplateModule Something (r:0 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: TemplateModule Something (r:1 w:1)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	fn do_something() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_000_000 picoseconds.
-		Weight::from_parts(9_000_000, 0)
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		Weight::from_parts(30_000_000, 13177)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: TemplateModule Something (r:1 w:1)
-	/// Proof: TemplateModule Something (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
 	fn cause_error() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `32`
-		//  Estimated: `1489`
-		// Minimum execution time: 6_000_000 picoseconds.
-		Weight::from_parts(6_000_000, 1489)
+		Weight::from_parts(14_000_000, 5487)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TemplateModule Something (r:1 w:1)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn clear_something() -> Weight {
+		Weight::from_parts(32_000_000, 15077)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TemplateModule Something (r:2 w:2)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:2 w:2)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn transfer_something() -> Weight {
+		Weight::from_parts(40_000_000, 18350)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: TemplateModule Something (r:0 w:1)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	fn force_set() -> Weight {
+		Weight::from_parts(14_000_000, 5487)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TemplateModule Something (r:1 w:1)
+	/// Proof: TemplateModule Something (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: TemplateModule History (r:1 w:1)
+	/// Proof: TemplateModule History (max_values: Some(1), max_size: Some(4002), added: 4497, mode: MaxEncodedLen)
+	/// Storage: Balances Holds (r:1 w:1)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn force_clear() -> Weight {
+		Weight::from_parts(32_000_000, 15077)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: TemplateModule Reporters (r:1 w:0)
+	/// Proof: TemplateModule Reporters (max_values: Some(1), max_size: Some(1025), added: 1520, mode: MaxEncodedLen)
+	/// Storage: TemplateModule OffchainTotal (r:0 w:1)
+	/// Proof: TemplateModule OffchainTotal (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	fn submit_total() -> Weight {
+		Weight::from_parts(11_000_000, 2510)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: TemplateModule OffchainTotal (r:0 w:1)
+	/// Proof: TemplateModule OffchainTotal (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: TemplateModule NextUnsignedAt (r:0 w:1)
+	/// Proof: TemplateModule NextUnsignedAt (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn submit_total_unsigned() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: TemplateModule Reporters (r:0 w:1)
+	/// Proof: TemplateModule Reporters (max_values: Some(1), max_size: Some(1025), added: 1520, mode: MaxEncodedLen)
+	fn set_reporters() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
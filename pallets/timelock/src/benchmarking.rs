@@ -0,0 +1,62 @@
+//! Benchmarking setup for pallet-timelock
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Timelock;
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Hash, Saturating};
+use sp_std::{boxed::Box, vec};
+
+fn remark_call<T: Config>() -> Box<<T as Config>::RuntimeCall> {
+	Box::new(frame_system::Call::<T>::remark { remark: vec![] }.into())
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn queue() -> Result<(), BenchmarkError> {
+		let origin =
+			T::QueueOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let call = remark_call::<T>();
+		let call_hash = T::Hashing::hash_of(&call);
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, call);
+
+		assert!(Queued::<T>::contains_key(call_hash));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn execute() {
+		let call = remark_call::<T>();
+		let call_hash = T::Hashing::hash_of(&call);
+		let eta = frame_system::Pallet::<T>::block_number().saturating_add(T::Delay::get());
+		Queued::<T>::insert(call_hash, eta);
+		frame_system::Pallet::<T>::set_block_number(eta);
+		let caller: T::AccountId = whitelisted_caller();
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), call);
+
+		assert!(!Queued::<T>::contains_key(call_hash));
+	}
+
+	#[benchmark]
+	fn cancel() -> Result<(), BenchmarkError> {
+		let origin =
+			T::CancelOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let call_hash = T::Hashing::hash_of(&remark_call::<T>());
+		Queued::<T>::insert(call_hash, T::Delay::get());
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, call_hash);
+
+		assert!(!Queued::<T>::contains_key(call_hash));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Timelock, crate::mock::new_test_ext(), crate::mock::Test);
+}
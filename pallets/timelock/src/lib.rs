@@ -0,0 +1,153 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Timelock Pallet
+//!
+//! Gives governance a root origin that can only act after a delay.
+//!
+//! `QueueOrigin` (typically a council supermajority) queues a call. Once `Delay` blocks have
+//! passed, any signed account can `execute` it and it is dispatched with the root origin.
+//! Until then `CancelOrigin` can drop it again. Only the hash of a queued call is stored, so the
+//! executor has to supply the same call that was queued.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		dispatch::{extract_actual_weight, GetDispatchInfo},
+		pallet_prelude::*,
+		traits::UnfilteredDispatchable,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, Saturating};
+	use sp_std::boxed::Box;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The calls that can be queued and dispatched.
+		type RuntimeCall: Parameter
+			+ UnfilteredDispatchable<RuntimeOrigin = Self::RuntimeOrigin>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+		/// Origin allowed to queue a call.
+		type QueueOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Origin allowed to cancel a queued call.
+		type CancelOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Number of blocks a call has to wait in the queue before it can be executed.
+		#[pallet::constant]
+		type Delay: Get<Self::BlockNumber>;
+		/// Type representing the weight of this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Hashes of queued calls, mapped to the first block in which they can be executed.
+	#[pallet::storage]
+	#[pallet::getter(fn queued)]
+	pub type Queued<T: Config> = StorageMap<_, Identity, T::Hash, T::BlockNumber>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A call was queued and can be executed from block `eta` on.
+		CallQueued { call_hash: T::Hash, eta: T::BlockNumber },
+		/// A queued call was dispatched with the root origin.
+		CallExecuted { call_hash: T::Hash, result: DispatchResult },
+		/// A queued call was cancelled.
+		CallCancelled { call_hash: T::Hash },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call is already in the queue.
+		AlreadyQueued,
+		/// The call is not in the queue.
+		NotQueued,
+		/// The delay of the queued call has not passed yet.
+		StillLocked,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Queue `call` for execution with the root origin after `Delay` blocks.
+		///
+		/// The dispatch origin for this call must be `QueueOrigin`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::queue())]
+		pub fn queue(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			T::QueueOrigin::ensure_origin(origin)?;
+
+			let call_hash = T::Hashing::hash_of(&call);
+			ensure!(!<Queued<T>>::contains_key(call_hash), Error::<T>::AlreadyQueued);
+
+			let eta = frame_system::Pallet::<T>::block_number().saturating_add(T::Delay::get());
+			<Queued<T>>::insert(call_hash, eta);
+
+			Self::deposit_event(Event::CallQueued { call_hash, eta });
+			Ok(())
+		}
+
+		/// Dispatch a queued `call` with the root origin once its delay has passed.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		#[pallet::call_index(1)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(T::WeightInfo::execute().saturating_add(dispatch_info.weight), dispatch_info.class)
+		})]
+		pub fn execute(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let call_hash = T::Hashing::hash_of(&call);
+			let eta = <Queued<T>>::get(call_hash).ok_or(Error::<T>::NotQueued)?;
+			ensure!(frame_system::Pallet::<T>::block_number() >= eta, Error::<T>::StillLocked);
+			<Queued<T>>::remove(call_hash);
+
+			let dispatch_info = call.get_dispatch_info();
+			let res = call.dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
+			let actual_weight = T::WeightInfo::execute()
+				.saturating_add(extract_actual_weight(&res, &dispatch_info));
+			Self::deposit_event(Event::CallExecuted {
+				call_hash,
+				result: res.map(|_| ()).map_err(|e| e.error),
+			});
+
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Remove a queued call before it is executed.
+		///
+		/// The dispatch origin for this call must be `CancelOrigin`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::cancel())]
+		pub fn cancel(origin: OriginFor<T>, call_hash: T::Hash) -> DispatchResult {
+			T::CancelOrigin::ensure_origin(origin)?;
+
+			<Queued<T>>::take(call_hash).ok_or(Error::<T>::NotQueued)?;
+
+			Self::deposit_event(Event::CallCancelled { call_hash });
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,80 @@
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
+
+/// A root-only call whose effect is easy to observe.
+fn set_storage_call() -> Box<RuntimeCall> {
+	Box::new(RuntimeCall::System(frame_system::Call::set_storage {
+		items: vec![(b"key".to_vec(), b"value".to_vec())],
+	}))
+}
+
+#[test]
+fn queue_requires_queue_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Timelock::queue(RuntimeOrigin::signed(2), set_storage_call()),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn queued_call_is_executed_as_root_after_the_delay() {
+	new_test_ext().execute_with(|| {
+		let call = set_storage_call();
+		let call_hash = BlakeTwo256::hash_of(&call);
+		assert_ok!(Timelock::queue(RuntimeOrigin::signed(1), call.clone()));
+		System::assert_last_event(Event::CallQueued { call_hash, eta: 1 + DELAY }.into());
+
+		// Anyone can try, but only once the delay has passed.
+		System::set_block_number(DELAY);
+		assert_noop!(
+			Timelock::execute(RuntimeOrigin::signed(3), call.clone()),
+			Error::<Test>::StillLocked
+		);
+
+		System::set_block_number(1 + DELAY);
+		assert_ok!(Timelock::execute(RuntimeOrigin::signed(3), call.clone()));
+		System::assert_last_event(Event::CallExecuted { call_hash, result: Ok(()) }.into());
+		assert_eq!(sp_io::storage::get(b"key").as_deref(), Some(&b"value"[..]));
+		assert_eq!(Timelock::queued(call_hash), None);
+
+		// A call only runs once per queueing.
+		assert_noop!(Timelock::execute(RuntimeOrigin::signed(3), call), Error::<Test>::NotQueued);
+	});
+}
+
+#[test]
+fn queue_rejects_duplicates() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Timelock::queue(RuntimeOrigin::signed(1), set_storage_call()));
+		assert_noop!(
+			Timelock::queue(RuntimeOrigin::signed(1), set_storage_call()),
+			Error::<Test>::AlreadyQueued
+		);
+	});
+}
+
+#[test]
+fn cancelled_call_cannot_be_executed() {
+	new_test_ext().execute_with(|| {
+		let call = set_storage_call();
+		let call_hash = BlakeTwo256::hash_of(&call);
+		assert_ok!(Timelock::queue(RuntimeOrigin::signed(1), call.clone()));
+
+		assert_noop!(
+			Timelock::cancel(RuntimeOrigin::signed(2), call_hash),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Timelock::cancel(RuntimeOrigin::signed(1), call_hash));
+		System::assert_last_event(Event::CallCancelled { call_hash }.into());
+
+		System::set_block_number(1 + DELAY);
+		assert_noop!(Timelock::execute(RuntimeOrigin::signed(3), call), Error::<Test>::NotQueued);
+		assert_eq!(sp_io::storage::get(b"key"), None);
+	});
+}
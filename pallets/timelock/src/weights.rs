@@ -0,0 +1,74 @@
+//! Weights for pallet_timelock
+//!
+//! These weights are estimated from the storage accesses of each call and have not been measured.
+//! Regenerate this file with the benchmark CLI before relying on them in production:
+//!
+//! ```text
+//! ./target/release/node-template benchmark pallet --chain dev --pallet pallet_timelock \
+//!     --extrinsic '*' --steps 50 --repeat 20 --output pallets/timelock/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_timelock.
+pub trait WeightInfo {
+	fn queue() -> Weight;
+	fn execute() -> Weight;
+	fn cancel() -> Weight;
+}
+
+/// Weights for pallet_timelock using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Timelock Queued (r:1 w:1)
+	/// Proof: Timelock Queued (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+	fn queue() -> Weight {
+		Weight::from_parts(12_000_000, 3501)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timelock Queued (r:1 w:1)
+	/// Proof: Timelock Queued (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+	fn execute() -> Weight {
+		Weight::from_parts(15_000_000, 3501)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timelock Queued (r:1 w:1)
+	/// Proof: Timelock Queued (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+	fn cancel() -> Weight {
+		Weight::from_parts(11_000_000, 3501)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: Timelock Queued (r:1 w:1)
+	/// Proof: Timelock Queued (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+	fn queue() -> Weight {
+		Weight::from_parts(12_000_000, 3501)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timelock Queued (r:1 w:1)
+	/// Proof: Timelock Queued (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+	fn execute() -> Weight {
+		Weight::from_parts(15_000_000, 3501)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Timelock Queued (r:1 w:1)
+	/// Proof: Timelock Queued (max_values: None, max_size: Some(36), added: 2511, mode: MaxEncodedLen)
+	fn cancel() -> Weight {
+		Weight::from_parts(11_000_000, 3501)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -0,0 +1,49 @@
+//! Benchmarking setup for pallet-validator-set
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ValidatorSet;
+use frame_benchmarking::v2::*;
+use frame_support::{traits::EnsureOrigin, BoundedVec};
+
+/// Fill the validator set with `n` benchmark accounts.
+fn set_validators<T: Config>(n: u32) {
+	let validators = (0..n).map(|i| account("validator", i, 0)).collect::<Vec<_>>();
+	<Validators<T>>::put(BoundedVec::truncate_from(validators));
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn add_validator() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AddRemoveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		set_validators::<T>(T::MaxValidators::get() - 1);
+		let who: T::AccountId = account("new", 0, 0);
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(ValidatorSet::<T>::validators().contains(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_validator() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AddRemoveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let max = T::MaxValidators::get();
+		set_validators::<T>(max);
+		// The last validator is the most expensive one to find.
+		let who: T::AccountId = account("validator", max - 1, 0);
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(!ValidatorSet::<T>::validators().contains(&who));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(ValidatorSet, crate::mock::new_test_ext(), crate::mock::Test);
+}
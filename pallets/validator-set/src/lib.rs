@@ -0,0 +1,272 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Validator Set Pallet
+//!
+//! Keeps the set of validators that `pallet_session` rotates in, and lets `AddRemoveOrigin`
+//! (typically root or a council supermajority) change it.
+//!
+//! The pallet acts as the [`pallet_session::SessionManager`]. Changes are stored right away but
+//! only handed to the session pallet at the end of the current session, which queues them for
+//! the session after. Validators without registered session keys are skipped by the session
+//! pallet until they call `Session::set_keys`.
+//!
+//! It also handles offences reported against validators, such as GRANDPA equivocations. There is
+//! no stake to slash, so an offender is disabled for the rest of the session and removed from the
+//! set instead, as long as `MinValidators` remain. This only happens if the `DisableStrategy` of
+//! the offence calls for disabling: always with `Always`, for a non-zero slash fraction with
+//! `WhenSlashed`, and never with `Never`. `AddRemoveOrigin` can add a removed validator back.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_runtime::{
+	traits::{Convert, Zero},
+	Perbill,
+};
+use sp_staking::{
+	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
+	SessionIndex,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ pallet_session::Config<ValidatorId = <Self as frame_system::Config>::AccountId>
+	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Origin allowed to add and remove validators.
+		type AddRemoveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Maximum number of validators.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+		/// Minimum number of validators that have to remain in the set.
+		#[pallet::constant]
+		type MinValidators: Get<u32>;
+		/// Type representing the weight of this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The validators handed to the session pallet, including changes not yet in effect.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+	/// Whether `Validators` changed since it was last handed to the session pallet.
+	#[pallet::storage]
+	pub type Changed<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The validators of the first sessions.
+		pub initial_validators: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { initial_validators: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let validators: BoundedVec<_, T::MaxValidators> =
+				self.initial_validators.clone().try_into().expect("too many initial validators");
+			<Validators<T>>::put(validators);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A validator was added. It takes part from the session after next on.
+		ValidatorAdded { who: T::AccountId },
+		/// A validator was removed. It leaves from the session after next on.
+		ValidatorRemoved { who: T::AccountId },
+		/// A validator was disabled for the rest of the session because of an offence.
+		ValidatorDisabled { who: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already a validator.
+		AlreadyValidator,
+		/// The account is not a validator.
+		NotValidator,
+		/// The validator set is full.
+		TooManyValidators,
+		/// Removing the validator would leave fewer than `MinValidators`.
+		TooFewValidators,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add `who` to the validator set.
+		///
+		/// The dispatch origin for this call must be `AddRemoveOrigin`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::add_validator())]
+		pub fn add_validator(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			<Validators<T>>::try_mutate(|validators| -> DispatchResult {
+				ensure!(!validators.contains(&who), Error::<T>::AlreadyValidator);
+				validators.try_push(who.clone()).map_err(|_| Error::<T>::TooManyValidators)?;
+				Ok(())
+			})?;
+			<Changed<T>>::put(true);
+
+			Self::deposit_event(Event::ValidatorAdded { who });
+			Ok(())
+		}
+
+		/// Remove `who` from the validator set.
+		///
+		/// The dispatch origin for this call must be `AddRemoveOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_validator())]
+		pub fn remove_validator(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			<Validators<T>>::try_mutate(|validators| -> DispatchResult {
+				let index =
+					validators.iter().position(|v| v == &who).ok_or(Error::<T>::NotValidator)?;
+				ensure!(
+					validators.len() as u32 > T::MinValidators::get(),
+					Error::<T>::TooFewValidators
+				);
+				validators.remove(index);
+				Ok(())
+			})?;
+			<Changed<T>>::put(true);
+
+			Self::deposit_event(Event::ValidatorRemoved { who });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Remove `who` from the validator set if enough validators remain. Returns whether it was
+	/// removed.
+	fn remove_offender(who: &T::AccountId) -> bool {
+		let removed =
+			<Validators<T>>::mutate(|validators| match validators.iter().position(|v| v == who) {
+				Some(index) if validators.len() as u32 > T::MinValidators::get() => {
+					validators.remove(index);
+					true
+				},
+				_ => false,
+			});
+		if removed {
+			<Changed<T>>::put(true);
+		}
+		removed
+	}
+}
+
+impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+	fn new_session(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+		if <Changed<T>>::take() {
+			Some(<Validators<T>>::get().into_inner())
+		} else {
+			None
+		}
+	}
+
+	fn new_session_genesis(_new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+		Some(<Validators<T>>::get().into_inner())
+	}
+
+	fn end_session(_end_index: SessionIndex) {}
+
+	fn start_session(_start_index: SessionIndex) {}
+}
+
+/// Validators carry no exposure, so they are fully identified by their account.
+impl<T: Config> pallet_session::historical::SessionManager<T::AccountId, ()> for Pallet<T> {
+	fn new_session(new_index: SessionIndex) -> Option<Vec<(T::AccountId, ())>> {
+		<Self as pallet_session::SessionManager<_>>::new_session(new_index)
+			.map(|validators| validators.into_iter().map(|v| (v, ())).collect())
+	}
+
+	fn new_session_genesis(new_index: SessionIndex) -> Option<Vec<(T::AccountId, ())>> {
+		<Self as pallet_session::SessionManager<_>>::new_session_genesis(new_index)
+			.map(|validators| validators.into_iter().map(|v| (v, ())).collect())
+	}
+
+	fn end_session(end_index: SessionIndex) {
+		<Self as pallet_session::SessionManager<_>>::end_session(end_index)
+	}
+
+	fn start_session(start_index: SessionIndex) {
+		<Self as pallet_session::SessionManager<_>>::start_session(start_index)
+	}
+}
+
+/// Identifies every validator by `()` for `pallet_session::historical`.
+pub struct FullIdentificationOf<T>(PhantomData<T>);
+
+impl<T: Config> Convert<T::AccountId, Option<()>> for FullIdentificationOf<T> {
+	fn convert(_validator: T::AccountId) -> Option<()> {
+		Some(())
+	}
+}
+
+impl<T: Config> OnOffenceHandler<T::AccountId, (T::AccountId, ()), Weight> for Pallet<T> {
+	fn on_offence(
+		offenders: &[OffenceDetails<T::AccountId, (T::AccountId, ())>],
+		slash_fraction: &[Perbill],
+		_session: SessionIndex,
+		disable_strategy: DisableStrategy,
+	) -> Weight {
+		let mut weight = Weight::zero();
+		for (index, details) in offenders.iter().enumerate() {
+			let (who, ()) = &details.offender;
+
+			// Removal stands in for the slash, so it follows the same strategy as disabling.
+			let punish = match disable_strategy {
+				DisableStrategy::Never => false,
+				DisableStrategy::WhenSlashed =>
+					slash_fraction.get(index).map_or(false, |fraction| !fraction.is_zero()),
+				DisableStrategy::Always => true,
+			};
+			if !punish {
+				continue
+			}
+
+			if pallet_session::Pallet::<T>::disable(who) {
+				Self::deposit_event(Event::ValidatorDisabled { who: who.clone() });
+			}
+			if Self::remove_offender(who) {
+				Self::deposit_event(Event::ValidatorRemoved { who: who.clone() });
+			}
+
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 4));
+		}
+		weight
+	}
+}
@@ -0,0 +1,148 @@
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use pallet_session::SessionManager;
+use sp_runtime::{DispatchError, Perbill};
+use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+
+#[test]
+fn genesis_validators_start_the_first_session() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+		assert_eq!(ValidatorSet::new_session_genesis(0), Some(vec![1, 2]));
+		// Nothing changed since genesis.
+		assert_eq!(ValidatorSet::new_session(1), None);
+	});
+}
+
+#[test]
+fn changes_require_add_remove_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::add_validator(RuntimeOrigin::signed(1), 3),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ValidatorSet::remove_validator(RuntimeOrigin::signed(1), 2),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn added_validator_is_handed_over_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 3));
+		System::assert_last_event(Event::ValidatorAdded { who: 3 }.into());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2, 3]);
+
+		assert_eq!(ValidatorSet::new_session(1), Some(vec![1, 2, 3]));
+		// The change is only handed over once.
+		assert_eq!(ValidatorSet::new_session(2), None);
+	});
+}
+
+#[test]
+fn removed_validator_is_handed_over_at_the_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::ValidatorRemoved { who: 1 }.into());
+
+		assert_eq!(ValidatorSet::new_session(1), Some(vec![2]));
+	});
+}
+
+#[test]
+fn add_validator_fails_for_duplicates_and_full_set() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::add_validator(RuntimeOrigin::root(), 1),
+			Error::<Test>::AlreadyValidator
+		);
+
+		assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 3));
+		assert_noop!(
+			ValidatorSet::add_validator(RuntimeOrigin::root(), 4),
+			Error::<Test>::TooManyValidators
+		);
+	});
+}
+
+#[test]
+fn remove_validator_keeps_the_minimum() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::remove_validator(RuntimeOrigin::root(), 3),
+			Error::<Test>::NotValidator
+		);
+
+		assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			ValidatorSet::remove_validator(RuntimeOrigin::root(), 2),
+			Error::<Test>::TooFewValidators
+		);
+	});
+}
+
+fn report_offence(who: u64, disable_strategy: DisableStrategy) {
+	let details = OffenceDetails { offender: (who, ()), reporters: vec![] };
+	ValidatorSet::on_offence(&[details], &[Perbill::from_percent(50)], 0, disable_strategy);
+}
+
+#[test]
+fn offender_is_disabled_and_removed() {
+	new_test_ext().execute_with(|| {
+		report_offence(1, DisableStrategy::WhenSlashed);
+
+		assert_eq!(Session::disabled_validators(), vec![0]);
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![2]);
+		System::assert_has_event(Event::ValidatorDisabled { who: 1 }.into());
+		System::assert_last_event(Event::ValidatorRemoved { who: 1 }.into());
+		assert_eq!(ValidatorSet::new_session(1), Some(vec![2]));
+	});
+}
+
+#[test]
+fn last_offenders_are_kept_in_the_set() {
+	new_test_ext().execute_with(|| {
+		report_offence(1, DisableStrategy::WhenSlashed);
+		report_offence(2, DisableStrategy::WhenSlashed);
+
+		// Validator 2 is disabled, but `MinValidators` keeps it in the set.
+		assert_eq!(Session::disabled_validators(), vec![0, 1]);
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![2]);
+		System::assert_last_event(Event::ValidatorDisabled { who: 2 }.into());
+	});
+}
+
+#[test]
+fn offender_is_kept_if_the_strategy_says_never() {
+	new_test_ext().execute_with(|| {
+		report_offence(1, DisableStrategy::Never);
+
+		assert!(Session::disabled_validators().is_empty());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn offender_is_kept_when_not_slashed() {
+	new_test_ext().execute_with(|| {
+		let details = OffenceDetails { offender: (1, ()), reporters: vec![] };
+		ValidatorSet::on_offence(&[details], &[Perbill::zero()], 0, DisableStrategy::WhenSlashed);
+
+		assert!(Session::disabled_validators().is_empty());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+	});
+}
+
+#[test]
+fn offender_is_disabled_and_removed_if_the_strategy_says_always() {
+	new_test_ext().execute_with(|| {
+		let details = OffenceDetails { offender: (1, ()), reporters: vec![] };
+		ValidatorSet::on_offence(&[details], &[Perbill::zero()], 0, DisableStrategy::Always);
+
+		assert_eq!(Session::disabled_validators(), vec![0]);
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![2]);
+	});
+}
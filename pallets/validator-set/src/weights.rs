@@ -0,0 +1,67 @@
+//! Weights for pallet_validator_set
+//!
+//! These weights are estimated from the storage accesses of each call and have not been measured.
+//! Regenerate this file with the benchmark CLI before relying on them in production:
+//!
+//! ```text
+//! ./target/release/node-template benchmark pallet --chain dev --pallet pallet_validator_set \
+//!     --extrinsic '*' --steps 50 --repeat 20 --output pallets/validator-set/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_validator_set.
+pub trait WeightInfo {
+	fn add_validator() -> Weight;
+	fn remove_validator() -> Weight;
+}
+
+/// Weights for pallet_validator_set using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: ValidatorSet Validators (r:1 w:1)
+	/// Proof: ValidatorSet Validators (max_values: Some(1), max_size: Some(1025), added: 1520, mode: MaxEncodedLen)
+	/// Storage: ValidatorSet Changed (r:0 w:1)
+	/// Proof: ValidatorSet Changed (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn add_validator() -> Weight {
+		Weight::from_parts(18_000_000, 2510)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: ValidatorSet Validators (r:1 w:1)
+	/// Proof: ValidatorSet Validators (max_values: Some(1), max_size: Some(1025), added: 1520, mode: MaxEncodedLen)
+	/// Storage: ValidatorSet Changed (r:0 w:1)
+	/// Proof: ValidatorSet Changed (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn remove_validator() -> Weight {
+		Weight::from_parts(17_000_000, 2510)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: ValidatorSet Validators (r:1 w:1)
+	/// Proof: ValidatorSet Validators (max_values: Some(1), max_size: Some(1025), added: 1520, mode: MaxEncodedLen)
+	/// Storage: ValidatorSet Changed (r:0 w:1)
+	/// Proof: ValidatorSet Changed (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn add_validator() -> Weight {
+		Weight::from_parts(18_000_000, 2510)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: ValidatorSet Validators (r:1 w:1)
+	/// Proof: ValidatorSet Validators (max_values: Some(1), max_size: Some(1025), added: 1520, mode: MaxEncodedLen)
+	/// Storage: ValidatorSet Changed (r:0 w:1)
+	/// Proof: ValidatorSet Changed (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	fn remove_validator() -> Weight {
+		Weight::from_parts(17_000_000, 2510)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
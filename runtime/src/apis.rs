@@ -0,0 +1,62 @@
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A call waiting in the agenda of the scheduler.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct AgendaItem<BlockNumber> {
+	/// Position of the call in the agenda of its block, as expected by `Scheduler::cancel`.
+	pub index: u32,
+	/// Name of the call if it was scheduled with `schedule_named`.
+	pub id: Option<[u8; 32]>,
+	/// Calls with a lower priority value are dispatched first.
+	pub priority: u8,
+	/// Hash of the encoded call. Calls that are not stored inline are noted in `Preimage` under
+	/// this hash.
+	pub call_hash: H256,
+	/// `(period, remaining repetitions)` of a recurring call.
+	pub periodic: Option<(BlockNumber, u32)>,
+}
+
+/// Metadata of the native token, which chain specs advertise in their properties.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct TokenMetadata {
+	/// Symbol of the token, as `tokenSymbol`.
+	pub symbol: Vec<u8>,
+	/// Number of decimals of the token, as `tokenDecimals`.
+	pub decimals: u8,
+	/// Prefix of the SS58 addresses of the chain, as `ss58Format`.
+	pub ss58_format: u16,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Quotes transaction fees in the assets that `ChargeAssetTxPayment` accepts.
+	pub trait AssetTxPaymentApi<AssetId, Balance>
+	where
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// The fee, tip included, that `uxt` of encoded length `len` would be charged in
+		/// `asset_id`, or `None` if the asset cannot pay fees.
+		fn query_fee_in_asset(uxt: Block::Extrinsic, len: u32, asset_id: AssetId) -> Option<Balance>;
+	}
+
+	/// Lists the calls waiting in the scheduler.
+	pub trait SchedulerApi<BlockNumber>
+	where
+		BlockNumber: Codec,
+	{
+		/// The calls scheduled for block `when`.
+		fn agenda(when: BlockNumber) -> Vec<AgendaItem<BlockNumber>>;
+		/// Every block that has calls scheduled, in ascending order, with its calls.
+		fn pending_agendas() -> Vec<(BlockNumber, Vec<AgendaItem<BlockNumber>>)>;
+	}
+
+	/// Describes the native token, so that the node can check the properties of its chain spec.
+	pub trait TokenApi {
+		/// Symbol, decimals and address format of the token.
+		fn token_metadata() -> TokenMetadata;
+	}
+}
@@ -0,0 +1,819 @@
+//! Some configurable implementations as associated type for the node template runtime.
+
+use crate::{
+	apis::{AgendaItem, TokenMetadata},
+	AccountId, AdjustmentVariable, AssetId, Assets, Authorship, Balance, Balances, BlockNumber,
+	FeeTreasuryShare, MaximumMultiplier, MinimumMultiplier, Runtime, RuntimeCall, SS58Prefix,
+	TargetBlockFullness, TipTreasuryShare, Treasury, TOKEN_DECIMALS, TOKEN_SYMBOL,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::{
+	fungibles::{Balanced, Credit},
+	tokens::ConversionToAssetBalance,
+	Bounded, Contains, Currency, Imbalance, InstanceFilter, OnUnbalanced,
+};
+use pallet_asset_tx_payment::HandleCredit;
+use pallet_transaction_payment::TargetedFeeAdjustment;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{ConvertInto, Zero},
+	Percent, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+/// Pays an imbalance to the author of the current block, or into the treasury if the block has
+/// no known author.
+pub struct Author;
+impl OnUnbalanced<NegativeImbalance> for Author {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		match Authorship::author() {
+			Some(author) => Balances::resolve_creating(&author, amount),
+			None => Treasury::on_unbalanced(amount),
+		}
+	}
+}
+
+/// Splits transaction fees and tips between the treasury and the block author, according to
+/// `FeeTreasuryShare` and `TipTreasuryShare`.
+pub struct DealWithFees;
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
+		if let Some(fees) = fees_then_tips.next() {
+			let (mut to_treasury, mut to_author) = split(fees, FeeTreasuryShare::get());
+			if let Some(tips) = fees_then_tips.next() {
+				let (tips_to_treasury, tips_to_author) = split(tips, TipTreasuryShare::get());
+				to_treasury.subsume(tips_to_treasury);
+				to_author.subsume(tips_to_author);
+			}
+			Treasury::on_unbalanced(to_treasury);
+			Author::on_unbalanced(to_author);
+		}
+	}
+}
+
+/// Splits `amount` into the `share` of it and the rest.
+fn split(amount: NegativeImbalance, share: Percent) -> (NegativeImbalance, NegativeImbalance) {
+	let first = share * amount.peek();
+	amount.split(first)
+}
+
+/// Converts native fees into fees in a sufficient asset, at the ratio of the asset's minimum
+/// balance to the existential deposit.
+pub type NativeToAssetBalance =
+	pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>;
+
+/// The fee charged in `asset_id` for a native `fee`, or `None` if the asset cannot pay fees.
+pub fn fee_in_asset(fee: Balance, asset_id: AssetId) -> Option<Balance> {
+	let converted = NativeToAssetBalance::to_asset_balance(fee, asset_id).ok()?;
+	// Like `FungiblesAdapter`, a non-zero fee never rounds down to nothing.
+	Some(if fee.is_zero() { converted } else { converted.max(1) })
+}
+
+/// Pays asset fees and tips to the author of the current block, or into the treasury if the
+/// block has no known author.
+pub struct CreditToBlockAuthor;
+impl HandleCredit<AccountId, Assets> for CreditToBlockAuthor {
+	fn handle_credit(credit: Credit<AccountId, Assets>) {
+		let who = Authorship::author().unwrap_or_else(Treasury::account_id);
+		// A credit that cannot be resolved, e.g. below the minimum balance, is burnt when dropped.
+		let _ = Assets::resolve(&who, credit);
+	}
+}
+
+/// Raises the fee multiplier while blocks are fuller than `TargetBlockFullness` and lowers it
+/// while they are emptier, by at most `AdjustmentVariable` per block, within
+/// `MinimumMultiplier..=MaximumMultiplier`.
+pub type SlowAdjustingFeeUpdate<R> = TargetedFeeAdjustment<
+	R,
+	TargetBlockFullness,
+	AdjustmentVariable,
+	MinimumMultiplier,
+	MaximumMultiplier,
+>;
+
+/// Calls that `CallFilter` never blocks: inherents, consensus and session key upkeep, and
+/// everything governance needs to lift a pause or safe mode again.
+pub struct UnfilterableCalls;
+impl Contains<RuntimeCall> for UnfilterableCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(_) |
+				RuntimeCall::Timestamp(_) |
+				RuntimeCall::Session(_) |
+				RuntimeCall::Grandpa(_) |
+				RuntimeCall::Council(_) |
+				RuntimeCall::CouncilMembership(_) |
+				RuntimeCall::Timelock(_) |
+				RuntimeCall::Scheduler(_) |
+				RuntimeCall::CallFilter(_)
+		)
+	}
+}
+
+/// The kinds of proxies an account can add in `Proxy`, each allowing a subset of calls.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum ProxyType {
+	/// Any call.
+	Any,
+	/// Any call that cannot move funds or values of `TemplateModule` away from the account.
+	NonTransfer,
+	/// Calls of `TemplateModule`, optionally batched.
+	Template,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, call: &RuntimeCall) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(
+				call,
+				RuntimeCall::Balances(..) |
+					RuntimeCall::Assets(..) |
+					RuntimeCall::Vesting(pallet_vesting::Call::vested_transfer { .. }) |
+					RuntimeCall::TemplateModule(pallet_template::Call::transfer_something { .. })
+			),
+			// Calls nested in a batch are checked against the proxy type on their own.
+			ProxyType::Template =>
+				matches!(call, RuntimeCall::TemplateModule(..) | RuntimeCall::Utility(..)),
+		}
+	}
+
+	fn is_superset(&self, other: &Self) -> bool {
+		match (self, other) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			// `Template` allows `transfer_something`, which `NonTransfer` does not.
+			_ => false,
+		}
+	}
+}
+
+/// The metadata of the native token.
+pub fn token_metadata() -> TokenMetadata {
+	TokenMetadata {
+		symbol: TOKEN_SYMBOL.as_bytes().to_vec(),
+		decimals: TOKEN_DECIMALS,
+		ss58_format: SS58Prefix::get(),
+	}
+}
+
+/// The calls scheduled for `when`.
+pub fn agenda(when: BlockNumber) -> Vec<AgendaItem<BlockNumber>> {
+	// The fields of `pallet_scheduler::Scheduled` are private, so the ones listed are decoded from
+	// its encoding, which starts with them.
+	#[derive(Decode)]
+	struct Scheduled {
+		maybe_id: Option<[u8; 32]>,
+		priority: u8,
+		call: Bounded<RuntimeCall>,
+		maybe_periodic: Option<(BlockNumber, u32)>,
+	}
+
+	pallet_scheduler::Agenda::<Runtime>::get(when)
+		.iter()
+		.enumerate()
+		.filter_map(|(index, scheduled)| {
+			let scheduled = Scheduled::decode(&mut &scheduled.as_ref()?.encode()[..]).ok()?;
+			Some(AgendaItem {
+				index: index as u32,
+				id: scheduled.maybe_id,
+				priority: scheduled.priority,
+				call_hash: scheduled.call.hash(),
+				periodic: scheduled.maybe_periodic,
+			})
+		})
+		.collect()
+}
+
+/// Every block with calls scheduled, in ascending order, with its calls.
+pub fn pending_agendas() -> Vec<(BlockNumber, Vec<AgendaItem<BlockNumber>>)> {
+	let mut blocks = pallet_scheduler::Agenda::<Runtime>::iter_keys().collect::<Vec<_>>();
+	blocks.sort_unstable();
+	blocks
+		.into_iter()
+		.map(|when| (when, agenda(when)))
+		// Agendas whose calls have all been dispatched or cancelled may be left behind empty.
+		.filter(|(_, items)| !items.is_empty())
+		.collect()
+}
+
+#[cfg(test)]
+mod multiplier_tests {
+	use super::*;
+	use crate::{mock::new_test_ext, BlockWeights, Runtime, System};
+	use frame_support::{dispatch::DispatchClass, weights::Weight};
+	use pallet_transaction_payment::Multiplier;
+	use sp_runtime::{
+		traits::{Convert, One, Zero},
+		FixedPointNumber, Perquintill,
+	};
+
+	fn max_normal() -> Weight {
+		BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_total
+			.unwrap_or_else(|| BlockWeights::get().max_block)
+	}
+
+	fn target() -> Weight {
+		TargetBlockFullness::get() * max_normal()
+	}
+
+	/// Runs `blocks` blocks that each use `weight`, starting from `multiplier`, and returns the
+	/// multiplier after every block.
+	fn simulate(multiplier: Multiplier, weight: Weight, blocks: usize) -> Vec<Multiplier> {
+		let mut multiplier = multiplier;
+		(0..blocks)
+			.map(|_| {
+				System::set_block_consumed_resources(weight, 0);
+				multiplier = SlowAdjustingFeeUpdate::<Runtime>::convert(multiplier);
+				multiplier
+			})
+			.collect()
+	}
+
+	#[test]
+	fn multiplier_is_stable_at_target_fullness() {
+		new_test_ext().execute_with(|| {
+			let curve = simulate(Multiplier::one(), target(), 100);
+			assert!(curve.iter().all(|m| *m == Multiplier::one()));
+		});
+	}
+
+	#[test]
+	fn full_blocks_raise_the_multiplier_faster_and_faster() {
+		new_test_ext().execute_with(|| {
+			let curve = simulate(Multiplier::one(), max_normal(), 1_000);
+
+			let mut previous = Multiplier::one();
+			let mut previous_step = Multiplier::zero();
+			for multiplier in curve {
+				assert!(multiplier > previous);
+				// Every step is a fixed share of the multiplier, so steps grow with it.
+				let step = multiplier - previous;
+				assert!(step >= previous_step);
+				previous = multiplier;
+				previous_step = step;
+			}
+			assert!(previous > Multiplier::saturating_from_rational(101, 100));
+		});
+	}
+
+	#[test]
+	fn empty_blocks_lower_the_multiplier_down_to_the_minimum() {
+		new_test_ext().execute_with(|| {
+			let start = Multiplier::saturating_from_rational(11, 10);
+			let curve = simulate(start, Weight::zero(), 20_000);
+
+			let mut previous = start;
+			for multiplier in curve.iter().copied() {
+				assert!(multiplier <= previous);
+				assert!(multiplier >= MinimumMultiplier::get());
+				previous = multiplier;
+			}
+			assert_eq!(previous, MinimumMultiplier::get());
+		});
+	}
+
+	#[test]
+	fn full_blocks_followed_by_empty_blocks_return_to_the_minimum() {
+		new_test_ext().execute_with(|| {
+			let full = simulate(MinimumMultiplier::get(), max_normal(), 500);
+			let peak = *full.last().unwrap();
+			assert!(peak > MinimumMultiplier::get());
+
+			let empty = simulate(peak, Weight::zero(), 20_000);
+			assert!(empty.windows(2).all(|w| w[1] <= w[0]));
+			assert_eq!(*empty.last().unwrap(), MinimumMultiplier::get());
+		});
+	}
+
+	#[test]
+	fn multiplier_never_exceeds_the_maximum() {
+		new_test_ext().execute_with(|| {
+			let curve = simulate(MaximumMultiplier::get(), max_normal(), 10);
+			assert!(curve.iter().all(|m| *m == MaximumMultiplier::get()));
+		});
+	}
+
+	#[test]
+	fn governance_can_change_the_target_fullness() {
+		new_test_ext().execute_with(|| {
+			// Blocks half full are above the default target and raise the multiplier.
+			let half_full = Perquintill::from_percent(50) * max_normal();
+			assert!(simulate(Multiplier::one(), half_full, 1)[0] > Multiplier::one());
+
+			// The same blocks are on target once the target is raised through `set_storage`.
+			TargetBlockFullness::set(&Perquintill::from_percent(50));
+			assert_eq!(simulate(Multiplier::one(), half_full, 1)[0], Multiplier::one());
+		});
+	}
+}
+
+#[cfg(test)]
+mod fee_tests {
+	use super::*;
+	use crate::{
+		mock::new_test_ext, RuntimeCall, RuntimeOrigin, System, TransactionPayment,
+		EXISTENTIAL_DEPOSIT,
+	};
+	use codec::Encode;
+	use frame_support::{
+		assert_ok,
+		dispatch::{GetDispatchInfo, Pays, PostDispatchInfo},
+		traits::ExistenceRequirement,
+	};
+	use pallet_asset_tx_payment::ChargeAssetTxPayment;
+	use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
+	use sp_keyring::Sr25519Keyring;
+	use sp_runtime::{traits::SignedExtension, Digest, DigestItem};
+
+	/// Starts a block authored in `slot`, or without an author if `slot` is `None`.
+	fn start_block(slot: Option<u64>) {
+		let logs = slot
+			.map(|slot| DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(slot).encode()))
+			.into_iter()
+			.collect();
+		System::initialize(&1, &Default::default(), &Digest { logs });
+	}
+
+	fn treasury_balance() -> u128 {
+		Balances::free_balance(Treasury::account_id())
+	}
+
+	fn balance(keyring: Sr25519Keyring) -> u128 {
+		Balances::free_balance(keyring.to_account_id())
+	}
+
+	const ASSET: AssetId = 1;
+	/// Ten units of the asset are worth one unit of the native token.
+	const ASSET_MIN_BALANCE: u128 = 10 * EXISTENTIAL_DEPOSIT;
+
+	/// Creates `ASSET` as a sufficient asset and mints `amount` of it to `who`.
+	fn create_asset(who: Sr25519Keyring, amount: u128) {
+		let owner = Sr25519Keyring::Ferdie.to_account_id();
+		assert_ok!(Assets::force_create(
+			RuntimeOrigin::root(),
+			ASSET.into(),
+			owner.clone().into(),
+			true,
+			ASSET_MIN_BALANCE,
+		));
+		assert_ok!(Assets::mint(
+			RuntimeOrigin::signed(owner),
+			ASSET.into(),
+			who.to_account_id().into(),
+			amount,
+		));
+	}
+
+	#[test]
+	fn fees_are_split_and_tips_go_to_the_author() {
+		new_test_ext().execute_with(|| {
+			// Bob is the second validator, so he authors odd slots.
+			start_block(Some(1));
+			let treasury = treasury_balance();
+
+			DealWithFees::on_unbalanceds(
+				vec![Balances::issue(10_000), Balances::issue(1_000)].into_iter(),
+			);
+
+			assert_eq!(treasury_balance(), treasury + 8_000);
+			assert_eq!(balance(Sr25519Keyring::Bob), 2_000 + 1_000);
+			assert_eq!(balance(Sr25519Keyring::Alice), 0);
+		});
+	}
+
+	#[test]
+	fn split_follows_the_stored_shares() {
+		new_test_ext().execute_with(|| {
+			start_block(Some(0));
+			let treasury = treasury_balance();
+			FeeTreasuryShare::set(&Percent::from_percent(50));
+			TipTreasuryShare::set(&Percent::from_percent(10));
+
+			DealWithFees::on_unbalanceds(
+				vec![Balances::issue(10_000), Balances::issue(1_000)].into_iter(),
+			);
+
+			assert_eq!(treasury_balance(), treasury + 5_000 + 100);
+			assert_eq!(balance(Sr25519Keyring::Alice), 5_000 + 900);
+		});
+	}
+
+	#[test]
+	fn everything_goes_to_the_treasury_without_an_author() {
+		new_test_ext().execute_with(|| {
+			start_block(None);
+			let treasury = treasury_balance();
+
+			DealWithFees::on_unbalanceds(
+				vec![Balances::issue(10_000), Balances::issue(1_000)].into_iter(),
+			);
+
+			assert_eq!(treasury_balance(), treasury + 11_000);
+		});
+	}
+
+	#[test]
+	fn dust_goes_to_the_treasury() {
+		new_test_ext().execute_with(|| {
+			start_block(None);
+			let alice = Sr25519Keyring::Alice.to_account_id();
+			let bob = Sr25519Keyring::Bob.to_account_id();
+			Balances::make_free_balance_be(&alice, 10_000);
+			let treasury = treasury_balance();
+
+			// Leaves Alice with less than the existential deposit.
+			assert!(<Balances as Currency<_>>::transfer(
+				&alice,
+				&bob,
+				9_900,
+				ExistenceRequirement::AllowDeath,
+			)
+			.is_ok());
+
+			assert_eq!(balance(Sr25519Keyring::Alice), 0);
+			assert_eq!(treasury_balance(), treasury + 100);
+		});
+	}
+
+	#[test]
+	fn asset_fees_are_quoted_at_the_asset_rate() {
+		new_test_ext().execute_with(|| {
+			create_asset(Sr25519Keyring::Charlie, 1_000_000);
+
+			assert_eq!(fee_in_asset(1_000, ASSET), Some(10_000));
+			assert_eq!(fee_in_asset(0, ASSET), Some(0));
+			// A fee worth less than one unit of the asset still costs one unit.
+			assert_eq!(fee_in_asset(1, ASSET), Some(10));
+			assert_eq!(fee_in_asset(1_000, ASSET + 1), None);
+		});
+	}
+
+	#[test]
+	fn fees_can_be_paid_in_a_sufficient_asset() {
+		new_test_ext().execute_with(|| {
+			start_block(Some(1));
+			let minted = 1_000_000_000_000_000;
+			create_asset(Sr25519Keyring::Charlie, minted);
+			let charlie = Sr25519Keyring::Charlie.to_account_id();
+			let bob = Sr25519Keyring::Bob.to_account_id();
+
+			let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+			let info = call.get_dispatch_info();
+			let len = 100;
+			let fee =
+				fee_in_asset(TransactionPayment::compute_fee(len as u32, &info, 0), ASSET).unwrap();
+
+			let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(ASSET))
+				.pre_dispatch(&charlie, &call, &info, len)
+				.unwrap();
+			assert_eq!(Assets::balance(ASSET, &charlie), minted - fee);
+
+			let post_info = PostDispatchInfo { actual_weight: None, pays_fee: Pays::Yes };
+			assert_ok!(ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				Some(pre),
+				&info,
+				&post_info,
+				len,
+				&Ok(()),
+			));
+			// Bob authored the block and Charlie never needed the native token.
+			assert_eq!(Assets::balance(ASSET, &bob), fee);
+			assert_eq!(Balances::free_balance(&charlie), 0);
+		});
+	}
+}
+
+#[cfg(test)]
+mod call_filter_tests {
+	use super::*;
+	use crate::{mock::new_test_ext, CallFilter, RuntimeOrigin};
+	use frame_support::assert_ok;
+	use sp_keyring::Sr25519Keyring;
+
+	fn name(name: &str) -> pallet_call_filter::PalletNameOf<Runtime> {
+		name.as_bytes().to_vec().try_into().unwrap()
+	}
+
+	fn transfer() -> RuntimeCall {
+		RuntimeCall::Balances(pallet_balances::Call::transfer {
+			dest: Sr25519Keyring::Bob.to_account_id().into(),
+			value: 1,
+		})
+	}
+
+	fn do_something() -> RuntimeCall {
+		RuntimeCall::TemplateModule(pallet_template::Call::do_something { something: 1 })
+	}
+
+	#[test]
+	fn paused_pallets_and_calls_are_filtered() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(CallFilter::pause(RuntimeOrigin::root(), name("TemplateModule"), None));
+			assert_ok!(CallFilter::pause(
+				RuntimeOrigin::root(),
+				name("Balances"),
+				Some(name("transfer"))
+			));
+
+			assert!(!CallFilter::contains(&do_something()));
+			assert!(!CallFilter::contains(&transfer()));
+			assert!(CallFilter::contains(&RuntimeCall::Balances(
+				pallet_balances::Call::transfer_keep_alive {
+					dest: Sr25519Keyring::Bob.to_account_id().into(),
+					value: 1,
+				}
+			)));
+		});
+	}
+
+	#[test]
+	fn safe_mode_keeps_inherents_and_governance_available() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(CallFilter::enter_safe_mode(RuntimeOrigin::root(), 10));
+
+			assert!(!CallFilter::contains(&do_something()));
+			assert!(!CallFilter::contains(&transfer()));
+			assert!(CallFilter::contains(&RuntimeCall::Timestamp(pallet_timestamp::Call::set {
+				now: 0
+			})));
+			assert!(CallFilter::contains(&RuntimeCall::CallFilter(
+				pallet_call_filter::Call::exit_safe_mode {}
+			)));
+		});
+	}
+}
+
+#[cfg(test)]
+mod scheduler_tests {
+	use super::*;
+	use crate::{mock::new_test_ext, CouncilCollective, RuntimeOrigin, Scheduler, System};
+	use frame_support::{assert_ok, traits::Hooks};
+	use sp_keyring::Sr25519Keyring;
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	fn payout(value: Balance) -> RuntimeCall {
+		RuntimeCall::Balances(pallet_balances::Call::force_transfer {
+			source: Treasury::account_id().into(),
+			dest: Sr25519Keyring::Bob.to_account_id().into(),
+			value,
+		})
+	}
+
+	/// The origin of a council motion that half of the members voted for.
+	fn council_half() -> RuntimeOrigin {
+		pallet_collective::RawOrigin::<AccountId, CouncilCollective>::Members(1, 2).into()
+	}
+
+	fn schedule(when: BlockNumber, call: RuntimeCall) {
+		assert_ok!(Scheduler::schedule(RuntimeOrigin::root(), when, None, 0, Box::new(call)));
+	}
+
+	#[test]
+	fn agenda_lists_scheduled_calls_per_block() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			schedule(10, payout(1));
+			schedule(5, payout(2));
+			assert_ok!(Scheduler::schedule_named(
+				RuntimeOrigin::root(),
+				[1; 32],
+				10,
+				Some((100, 3)),
+				1,
+				Box::new(payout(3)),
+			));
+
+			assert_eq!(
+				agenda(10),
+				vec![
+					AgendaItem {
+						index: 0,
+						id: None,
+						priority: 0,
+						call_hash: BlakeTwo256::hash_of(&payout(1)),
+						periodic: None,
+					},
+					AgendaItem {
+						index: 1,
+						id: Some([1; 32]),
+						priority: 1,
+						call_hash: BlakeTwo256::hash_of(&payout(3)),
+						periodic: Some((100, 3)),
+					},
+				]
+			);
+			assert_eq!(
+				pending_agendas()
+					.into_iter()
+					.map(|(when, items)| (when, items.len()))
+					.collect::<Vec<_>>(),
+				vec![(5, 1), (10, 2)]
+			);
+
+			// Cancelled calls leave a gap, so the other indices stay valid.
+			assert_ok!(Scheduler::cancel(RuntimeOrigin::root(), 10, 0));
+			assert_eq!(agenda(10).iter().map(|item| item.index).collect::<Vec<_>>(), vec![1]);
+		});
+	}
+
+	#[test]
+	fn scheduled_calls_are_dispatched_with_their_origin() {
+		new_test_ext().execute_with(|| {
+			let _ = Balances::deposit_creating(&Treasury::account_id(), 1_000_000);
+			System::set_block_number(1);
+			schedule(3, payout(1_000));
+
+			Scheduler::on_initialize(2);
+			assert_eq!(Balances::free_balance(Sr25519Keyring::Bob.to_account_id()), 0);
+
+			System::set_block_number(3);
+			Scheduler::on_initialize(3);
+			assert_eq!(Balances::free_balance(Sr25519Keyring::Bob.to_account_id()), 1_000);
+			assert!(pending_agendas().is_empty());
+		});
+	}
+
+	#[test]
+	fn only_the_scheduling_origin_can_cancel() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Scheduler::schedule(council_half(), 10, None, 0, Box::new(payout(1))));
+
+			assert!(Scheduler::cancel(RuntimeOrigin::root(), 10, 0).is_err());
+			assert_eq!(agenda(10).len(), 1);
+			assert_ok!(Scheduler::cancel(council_half(), 10, 0));
+			assert!(agenda(10).is_empty());
+		});
+	}
+}
+
+#[cfg(test)]
+mod proxy_tests {
+	use super::*;
+	use crate::{Multisig, Proxy, RuntimeOrigin, System, TemplateModule, Utility};
+	use frame_support::{assert_ok, dispatch::GetDispatchInfo, weights::Weight};
+	use sp_keyring::Sr25519Keyring::{Alice, Bob, Charlie};
+	use sp_runtime::traits::Dispatchable;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut ext = crate::mock::new_test_ext();
+		ext.execute_with(|| {
+			for who in [Alice, Bob, Charlie] {
+				Balances::make_free_balance_be(&who.to_account_id(), 1_000_000_000);
+			}
+		});
+		ext
+	}
+
+	fn transfer(value: Balance) -> RuntimeCall {
+		RuntimeCall::Balances(pallet_balances::Call::transfer {
+			dest: Charlie.to_account_id().into(),
+			value,
+		})
+	}
+
+	fn do_something(something: u32) -> RuntimeCall {
+		RuntimeCall::TemplateModule(pallet_template::Call::do_something { something })
+	}
+
+	fn batch_all(calls: Vec<RuntimeCall>) -> RuntimeCall {
+		RuntimeCall::Utility(pallet_utility::Call::batch_all { calls })
+	}
+
+	#[test]
+	fn proxy_types_filter_calls() {
+		assert!(ProxyType::Any.filter(&transfer(1)));
+		assert!(!ProxyType::NonTransfer.filter(&transfer(1)));
+		assert!(ProxyType::NonTransfer.filter(&do_something(1)));
+		assert!(!ProxyType::NonTransfer.filter(&RuntimeCall::TemplateModule(
+			pallet_template::Call::transfer_something { dest: Charlie.to_account_id().into() }
+		)));
+		assert!(ProxyType::Template.filter(&do_something(1)));
+		assert!(!ProxyType::Template.filter(&transfer(1)));
+
+		assert!(ProxyType::Any.is_superset(&ProxyType::Template));
+		assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Template));
+		assert!(!ProxyType::Template.is_superset(&ProxyType::Any));
+	}
+
+	#[test]
+	fn template_proxy_cannot_batch_a_transfer() {
+		new_test_ext().execute_with(|| {
+			let alice = Alice.to_account_id();
+			assert_ok!(Proxy::add_proxy(
+				RuntimeOrigin::signed(alice.clone()),
+				Bob.to_account_id().into(),
+				ProxyType::Template,
+				0
+			));
+
+			assert_ok!(Proxy::proxy(
+				RuntimeOrigin::signed(Bob.to_account_id()),
+				alice.clone().into(),
+				None,
+				Box::new(do_something(7)),
+			));
+			assert_eq!(TemplateModule::something(&alice).map(|info| info.value), Some(7));
+
+			let balance = Balances::free_balance(&alice);
+			assert_ok!(Proxy::proxy(
+				RuntimeOrigin::signed(Bob.to_account_id()),
+				alice.clone().into(),
+				None,
+				Box::new(batch_all(vec![do_something(8), transfer(1_000)])),
+			));
+			System::assert_last_event(
+				pallet_proxy::Event::ProxyExecuted {
+					result: Err(frame_system::Error::<Runtime>::CallFiltered.into()),
+				}
+				.into(),
+			);
+			assert_eq!(TemplateModule::something(&alice).map(|info| info.value), Some(7));
+			assert_eq!(Balances::free_balance(&alice), balance);
+		});
+	}
+
+	#[test]
+	fn multisig_dispatches_once_the_threshold_is_reached() {
+		new_test_ext().execute_with(|| {
+			let mut signatories =
+				[Alice, Bob, Charlie].iter().map(|who| who.to_account_id()).collect::<Vec<_>>();
+			signatories.sort();
+			let others = |who: &AccountId| {
+				signatories.iter().filter(|s| *s != who).cloned().collect::<Vec<_>>()
+			};
+			let multisig = Multisig::multi_account_id(&signatories, 2);
+			Balances::make_free_balance_be(&multisig, 10_000);
+			let call = transfer(1_000);
+			let charlie = Charlie.to_account_id();
+			let balance = Balances::free_balance(&charlie);
+
+			let alice = Alice.to_account_id();
+			assert_ok!(Multisig::as_multi(
+				RuntimeOrigin::signed(alice.clone()),
+				2,
+				others(&alice),
+				None,
+				Box::new(call.clone()),
+				Weight::zero(),
+			));
+			assert_eq!(Balances::free_balance(&charlie), balance);
+
+			let bob = Bob.to_account_id();
+			assert_ok!(Multisig::as_multi(
+				RuntimeOrigin::signed(bob.clone()),
+				2,
+				others(&bob),
+				Some(Multisig::timepoint()),
+				Box::new(call.clone()),
+				call.get_dispatch_info().weight,
+			));
+			assert_eq!(Balances::free_balance(&charlie), balance + 1_000);
+			assert_eq!(Balances::free_balance(&multisig), 9_000);
+		});
+	}
+
+	#[test]
+	fn batch_all_reverts_every_call_if_one_fails() {
+		new_test_ext().execute_with(|| {
+			let alice = Alice.to_account_id();
+			let balance = Balances::free_balance(&alice);
+
+			assert!(batch_all(vec![do_something(1), transfer(balance * 2)])
+				.dispatch(RuntimeOrigin::signed(alice.clone()))
+				.is_err());
+			assert_eq!(TemplateModule::something(&alice), None);
+			assert_eq!(Balances::free_balance(&alice), balance);
+
+			assert_ok!(Utility::batch_all(
+				RuntimeOrigin::signed(alice.clone()),
+				vec![do_something(1), transfer(1_000)]
+			));
+			assert_eq!(TemplateModule::something(&alice).map(|info| info.value), Some(1));
+		});
+	}
+}
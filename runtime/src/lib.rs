@@ -6,17 +6,24 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{AsEnsureOriginWithArg, EqualPrivilegeOnly},
+	PalletId,
+};
 use pallet_grandpa::AuthorityId as GrandpaId;
+use scale_info::TypeInfo;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		self, AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, IdentifyAccount,
+		NumberFor, One, OpaqueKeys, SaturatedConversion, StaticLookup, Verify,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, MultiSignature, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -27,7 +34,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstU128, ConstU32, ConstU64, ConstU8, EitherOfDiverse, KeyOwnerProofSystem, Randomness,
+		StorageInfo, WithdrawReasons,
 	},
 	weights::{
 		constants::{
@@ -38,16 +46,34 @@ pub use frame_support::{
 	StorageValue,
 };
 pub use frame_system::Call as SystemCall;
+use frame_system::{EnsureRoot, EnsureSigned, EnsureWithSuccess};
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
+use pallet_transaction_payment::{CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{FixedPointNumber, Perbill, Percent, Permill, Perquintill};
+
+/// Runtime APIs of this runtime that are not tied to a single pallet.
+pub mod apis;
+/// Implementations of some helper traits passed into runtime modules as associated types.
+pub mod impls;
+/// Storage migrations run on the next runtime upgrade.
+pub mod migrations;
+#[cfg(test)]
+mod mock;
+use impls::{
+	CreditToBlockAuthor, DealWithFees, NativeToAssetBalance, ProxyType, SlowAdjustingFeeUpdate,
+	UnfilterableCalls,
+};
+use migrations::Migrations;
 
 /// Import the template pallet.
 pub use pallet_template;
 
+/// Import the session pallet, whose storage the node reads from genesis.
+pub use pallet_session;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -64,6 +90,9 @@ pub type Balance = u128;
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
+/// Identifier of an asset of the `Assets` pallet.
+pub type AssetId = u32;
+
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
@@ -101,12 +130,14 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the runtime specification. A full node will not attempt to use its native
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
+	// Starting at 100 notifies Polkadot-JS App (https://polkadot.js.org/apps) to use the
+	//   compatible custom types; bump it with every runtime upgrade.
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	// Bumped whenever the encoding of extrinsics changes, e.g. the signed extensions or the call
+	//   indices, so that offline signers stop producing transactions the runtime rejects.
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -146,14 +177,15 @@ parameter_types! {
 		);
 	pub BlockLength: frame_system::limits::BlockLength = frame_system::limits::BlockLength
 		::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
-	pub const SS58Prefix: u8 = 42;
+	/// Prefix of the SS58 addresses of the chain, advertised as `ss58Format` by chain specs.
+	pub const SS58Prefix: u16 = 42;
 }
 
 // Configure FRAME pallets to include in runtime.
 
 impl frame_system::Config for Runtime {
-	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	/// The basic call filter to use in dispatchable. Governance can pause calls through it.
+	type BaseCallFilter = CallFilter;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = BlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -203,21 +235,79 @@ impl frame_system::Config for Runtime {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+parameter_types! {
+	/// Most validators, and so Aura and GRANDPA authorities, the chain can have.
+	pub const MaxAuthorities: u32 = 32;
+}
+
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
-	type DisabledValidators = ();
-	type MaxAuthorities = ConstU32<32>;
+	type DisabledValidators = Session;
+	type MaxAuthorities = MaxAuthorities;
+}
+
+parameter_types! {
+	/// Number of sessions for which equivocations can still be reported.
+	pub const MaxSetIdSessionEntries: u64 = 168;
+	/// Number of blocks an equivocation report stays valid in the transaction pool.
+	pub const ReportLongevity: u64 = MaxSetIdSessionEntries::get() * SessionPeriod::get() as u64;
 }
 
 impl pallet_grandpa::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 
 	type WeightInfo = ();
-	type MaxAuthorities = ConstU32<32>;
-	type MaxSetIdSessionEntries = ConstU64<0>;
+	type MaxAuthorities = MaxAuthorities;
+	type MaxSetIdSessionEntries = MaxSetIdSessionEntries;
+
+	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
+	type EquivocationReportSystem =
+		pallet_grandpa::EquivocationReportSystem<Self, Offences, Historical, ReportLongevity>;
+}
+
+/// Offences are handled by the validator set, which disables and removes the offender.
+impl pallet_offences::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
+	type OnOffenceHandler = ValidatorSet;
+}
+
+parameter_types! {
+	/// Number of blocks in a session. Validator set changes take effect at session boundaries.
+	pub const SessionPeriod: BlockNumber = 10 * MINUTES;
+	pub const SessionOffset: BlockNumber = 0;
+}
 
-	type KeyOwnerProof = sp_core::Void;
-	type EquivocationReportSystem = ();
+impl pallet_session::historical::Config for Runtime {
+	type FullIdentification = ();
+	type FullIdentificationOf = pallet_validator_set::FullIdentificationOf<Runtime>;
+}
+
+impl pallet_session::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, ValidatorSet>;
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
+/// Root, or two thirds of the council, may add and remove validators.
+impl pallet_validator_set::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AddRemoveOrigin = EnsureRootOrCouncilTwoThirds;
+	type MaxValidators = MaxAuthorities;
+	type MinValidators = ConstU32<1>;
+	type WeightInfo = pallet_validator_set::weights::SubstrateWeight<Runtime>;
+}
+
+/// The author of a block is the session validator at the index Aura picked for its slot.
+impl pallet_authorship::Config for Runtime {
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	type EventHandler = ();
 }
 
 impl pallet_timestamp::Config for Runtime {
@@ -231,7 +321,34 @@ impl pallet_timestamp::Config for Runtime {
 /// Existential deposit.
 pub const EXISTENTIAL_DEPOSIT: u128 = 500;
 
+/// Symbol of the native token, advertised as `tokenSymbol` by chain specs.
+pub const TOKEN_SYMBOL: &str = "UNIT";
+
+/// Number of decimals of the native token, advertised as `tokenDecimals` by chain specs.
+pub const TOKEN_DECIMALS: u8 = 12;
+
+/// The reasons for which balances can be put on hold in this runtime.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	RuntimeDebug,
+	TypeInfo,
+)]
+pub enum HoldReason {
+	/// Storage deposit for a value kept in `TemplateModule::Something`.
+	TemplateStorage,
+}
+
 impl pallet_balances::Config for Runtime {
+	/// `Vesting` takes a single lock however many schedules an account has, so this only has to
+	/// cover the lock identifiers in use.
 	type MaxLocks = ConstU32<50>;
 	type MaxReserves = ();
 	type ReserveIdentifier = [u8; 8];
@@ -239,38 +356,395 @@ impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	/// The ubiquitous event type.
 	type RuntimeEvent = RuntimeEvent;
-	type DustRemoval = ();
+	type DustRemoval = Treasury;
 	type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type HoldIdentifier = ();
-	type MaxHolds = ();
+	type HoldIdentifier = HoldReason;
+	/// One for each variant of `HoldReason`.
+	type MaxHolds = ConstU32<1>;
 }
 
+// The fee multiplier parameters live in storage, so that governance can tune them with a root
+// `System::set_storage` call instead of a runtime upgrade.
 parameter_types! {
-	pub FeeMultiplier: Multiplier = Multiplier::one();
+	/// Share of the normal dispatch weight of a block that keeps the fee multiplier unchanged.
+	pub storage TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// How fast the fee multiplier reacts to blocks above or below the target.
+	pub storage AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	/// Fees never drop below the weight and length fee of an empty chain.
+	pub storage MinimumMultiplier: Multiplier = Multiplier::one();
+	/// Fees never grow beyond this multiple of the weight and length fee.
+	pub storage MaximumMultiplier: Multiplier = Multiplier::saturating_from_integer(100_000);
+}
+
+// Like the fee multiplier parameters, the fee split can be changed with `System::set_storage`.
+parameter_types! {
+	/// Share of transaction fees paid into the treasury. The block author gets the rest.
+	pub storage FeeTreasuryShare: Percent = Percent::from_percent(80);
+	/// Share of transaction tips paid into the treasury. The block author gets the rest.
+	pub storage TipTreasuryShare: Percent = Percent::from_percent(0);
 }
 
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = IdentityFee<Balance>;
 	type LengthToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ConstFeeMultiplier<FeeMultiplier>;
+	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 1_000 * EXISTENTIAL_DEPOSIT;
+	pub const AssetAccountDeposit: Balance = EXISTENTIAL_DEPOSIT;
+	pub const ApprovalDeposit: Balance = EXISTENTIAL_DEPOSIT;
+	pub const MetadataDepositBase: Balance = 10 * EXISTENTIAL_DEPOSIT;
+	pub const MetadataDepositPerByte: Balance = EXISTENTIAL_DEPOSIT;
+	pub const AssetsStringLimit: u32 = 50;
+}
+
+/// Anyone can create an asset. Only root can create sufficient assets, which are the ones that
+/// can pay transaction fees.
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type AssetIdParameter = codec::Compact<AssetId>;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type AssetAccountDeposit = AssetAccountDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+/// Transactions may pay their fees in a sufficient asset instead of the native token. Asset fees
+/// and tips go to the block author.
+impl pallet_asset_tx_payment::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Fungibles = Assets;
+	type OnChargeAssetTransaction =
+		pallet_asset_tx_payment::FungiblesAdapter<NativeToAssetBalance, CreditToBlockAuthor>;
+}
+
+parameter_types! {
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
+	pub MaxCouncilProposalWeight: Weight = Perbill::from_percent(50) * BlockWeights::get().max_block;
+}
+
+type CouncilCollective = pallet_collective::Instance1;
+
+/// At least half of the council.
+pub type EnsureCouncilHalf =
+	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>;
+/// At least two thirds of the council.
+pub type EnsureCouncilTwoThirds =
+	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+/// Root, or at least half of the council.
+pub type EnsureRootOrCouncilHalf = EitherOfDiverse<EnsureRoot<AccountId>, EnsureCouncilHalf>;
+/// Root, or at least two thirds of the council.
+pub type EnsureRootOrCouncilTwoThirds =
+	EitherOfDiverse<EnsureRoot<AccountId>, EnsureCouncilTwoThirds>;
+
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+	type SetMembersOrigin = EnsureRoot<AccountId>;
+	type MaxProposalWeight = MaxCouncilProposalWeight;
+}
+
+/// Council members are managed through the membership pallet, which keeps the collective in sync.
+impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AddOrigin = EnsureRootOrCouncilHalf;
+	type RemoveOrigin = EnsureRootOrCouncilHalf;
+	type SwapOrigin = EnsureRootOrCouncilHalf;
+	type ResetOrigin = EnsureRootOrCouncilHalf;
+	type PrimeOrigin = EnsureRootOrCouncilHalf;
+	type MembershipInitialized = Council;
+	type MembershipChanged = Council;
+	type MaxMembers = CouncilMaxMembers;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Blocks a council decision waits in the timelock before it is dispatched as root. Two
+	/// minutes with the `fast-runtime` feature, so that development chains can use root calls.
+	pub const TimelockDelay: BlockNumber =
+		if cfg!(feature = "fast-runtime") { 2 * MINUTES } else { 2 * DAYS };
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	/// Share of a proposed spend that the proposer bonds, slashed if the proposal is rejected.
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	/// Blocks between two payouts of approved proposals.
+	pub const SpendPeriod: BlockNumber = DAYS;
+	/// Funds left after a payout stay in the treasury.
+	pub const Burn: Permill = Permill::zero();
+	pub const MaxApprovals: u32 = 100;
+	pub const MaxTreasurySpend: Balance = Balance::MAX;
+}
+
+/// The treasury collects its share of fees and tips, dust and rejected proposal bonds. Spending
+/// needs two thirds of the council to approve a proposal, or a root `spend` through the timelock.
+impl pallet_treasury::Config for Runtime {
+	type PalletId = TreasuryPalletId;
+	type Currency = Balances;
+	type ApproveOrigin = EnsureRootOrCouncilTwoThirds;
+	type RejectOrigin = EnsureRootOrCouncilHalf;
+	type RuntimeEvent = RuntimeEvent;
+	type OnSlash = Treasury;
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type ProposalBondMaximum = ();
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type SpendFunds = ();
+	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+	type MaxApprovals = MaxApprovals;
+	type SpendOrigin = EnsureWithSuccess<EnsureRoot<AccountId>, AccountId, MaxTreasurySpend>;
+}
+
+/// The root origin of this runtime is only reachable through the timelock: a two-thirds council
+/// motion queues a call, which anyone can execute once `TimelockDelay` has passed.
+impl pallet_timelock::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type QueueOrigin = EnsureCouncilTwoThirds;
+	type CancelOrigin = EnsureCouncilHalf;
+	type Delay = TimelockDelay;
+	type WeightInfo = pallet_timelock::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Longest safe mode that can be entered at once; governance can enter it again afterwards.
+	pub const MaxSafeModeDuration: BlockNumber = DAYS;
+}
+
+/// Half of the council can pause calls or enter safe mode right away, without going through the
+/// timelock. Calls in `UnfilterableCalls` stay available so the council can undo it.
+impl pallet_call_filter::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WhitelistedCalls = UnfilterableCalls;
+	type PauseOrigin = EnsureRootOrCouncilHalf;
+	type SafeModeOrigin = EnsureRootOrCouncilHalf;
+	type MaxSafeModeDuration = MaxSafeModeDuration;
+	type MaxNameLen = ConstU32<32>;
+	type WeightInfo = pallet_call_filter::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const PreimageBaseDeposit: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	pub const PreimageByteDeposit: Balance = EXISTENTIAL_DEPOSIT;
 }
 
-impl pallet_sudo::Config for Runtime {
+/// Calls too long to be scheduled inline are noted here first. The deposit is reserved from the
+/// account that notes a preimage, and root can note and release preimages for free.
+impl pallet_preimage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
+
+parameter_types! {
+	/// Scheduled calls may use at most this much of a block, the rest is left to transactions.
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+/// Root and half of the council can schedule calls, which are dispatched with the origin that
+/// scheduled them. Only that origin can cancel them again.
+impl pallet_scheduler::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type PalletsOrigin = OriginCaller;
+	type RuntimeCall = RuntimeCall;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRootOrCouncilHalf;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type Preimages = Preimage;
+}
+
+/// Calls can be batched, with `batch_all` reverting the whole batch if one of them fails.
+impl pallet_utility::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Reserved by the account that opens a multisig operation, for its storage item.
+	pub const MultisigDepositBase: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	/// Reserved per signatory of an open multisig operation.
+	pub const MultisigDepositFactor: Balance = 32 * EXISTENTIAL_DEPOSIT;
+	pub const MaxSignatories: u32 = 100;
+}
+
+/// Accounts controlled by a threshold of signatories. Their calls are dispatched once enough
+/// signatories approved them.
+impl pallet_multisig::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type DepositBase = MultisigDepositBase;
+	type DepositFactor = MultisigDepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	/// Reserved by an account for its list of proxies.
+	pub const ProxyDepositBase: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	/// Reserved per proxy of an account.
+	pub const ProxyDepositFactor: Balance = 33 * EXISTENTIAL_DEPOSIT;
+	pub const MaxProxies: u16 = 32;
+	/// Reserved by a proxy for its list of announced calls.
+	pub const AnnouncementDepositBase: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	/// Reserved per announced call of a proxy.
+	pub const AnnouncementDepositFactor: Balance = 68 * EXISTENTIAL_DEPOSIT;
+	pub const MaxPending: u16 = 32;
+}
+
+/// Accounts can let other accounts dispatch calls on their behalf, limited by a `ProxyType`.
+impl pallet_proxy::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	/// Locked funds can still pay for fees and tips.
+	pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+		WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+
+/// Balances that unlock linearly, from genesis or from `vested_transfer`.
+impl pallet_vesting::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+	/// Schedules of an account are merged into one lock, see `MaxLocks`.
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+parameter_types! {
+	pub const TemplateHoldReason: HoldReason = HoldReason::TemplateStorage;
+	/// Deposit held for every value stored in `TemplateModule::Something`.
+	pub const TemplateSomethingDeposit: Balance = 100 * EXISTENTIAL_DEPOSIT;
+	/// Number of value changes kept in `TemplateModule::History`.
+	pub const TemplateMaxHistory: u32 = 100;
+	/// Accounts allowed to report totals of the template offchain worker, as many as there can
+	/// be validators.
+	pub const TemplateMaxReporters: u32 = 32;
+	/// Blocks between two unsigned transactions of the template offchain worker.
+	pub const TemplateUnsignedInterval: BlockNumber = MINUTES;
+	pub const TemplateUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 }
 
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type HoldReason = TemplateHoldReason;
+	type SomethingDeposit = TemplateSomethingDeposit;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type MaxHistory = TemplateMaxHistory;
+	type MaxReporters = TemplateMaxReporters;
+	type AuthorityId = pallet_template::crypto::TemplateAuthId;
+	type UnsignedInterval = TemplateUnsignedInterval;
+	type UnsignedPriority = TemplateUnsignedPriority;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(RuntimeCall, <UncheckedExtrinsic as traits::Extrinsic>::SignaturePayload)> {
+		let tip = 0;
+		// Take the biggest period possible.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		// The `System::block_number` is initialized with `n+1`, so the actual block number is `n`.
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let era = generic::Era::mortal(period, current_block);
+		let extra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(era),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let address = <Runtime as frame_system::Config>::Lookup::unlookup(account);
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (address, signature, extra)))
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = UncheckedExtrinsic;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -283,11 +757,30 @@ construct_runtime!(
 	{
 		System: frame_system,
 		Timestamp: pallet_timestamp,
+		// Must be before `Session` and the consensus pallets, whose genesis and session changes
+		// depend on it.
+		ValidatorSet: pallet_validator_set,
+		Session: pallet_session,
+		Historical: pallet_session::historical,
+		Offences: pallet_offences,
 		Aura: pallet_aura,
 		Grandpa: pallet_grandpa,
+		Authorship: pallet_authorship,
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
-		Sudo: pallet_sudo,
+		Assets: pallet_assets,
+		AssetTxPayment: pallet_asset_tx_payment,
+		Council: pallet_collective::<Instance1>,
+		CouncilMembership: pallet_membership::<Instance1>,
+		Timelock: pallet_timelock,
+		CallFilter: pallet_call_filter,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		Utility: pallet_utility,
+		Multisig: pallet_multisig,
+		Proxy: pallet_proxy,
+		Vesting: pallet_vesting,
+		Treasury: pallet_treasury,
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
 	}
@@ -308,7 +801,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -323,6 +816,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -335,7 +829,20 @@ mod benches {
 		[frame_benchmarking, BaselineBench::<Runtime>]
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_assets, Assets]
 		[pallet_timestamp, Timestamp]
+		[pallet_collective, Council]
+		[pallet_membership, CouncilMembership]
+		[pallet_timelock, Timelock]
+		[pallet_call_filter, CallFilter]
+		[pallet_preimage, Preimage]
+		[pallet_scheduler, Scheduler]
+		[pallet_utility, Utility]
+		[pallet_multisig, Multisig]
+		[pallet_proxy, Proxy]
+		[pallet_vesting, Vesting]
+		[pallet_treasury, Treasury]
+		[pallet_validator_set, ValidatorSet]
 		[pallet_template, TemplateModule]
 	);
 }
@@ -438,23 +945,24 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: sp_consensus_grandpa::EquivocationProof<
+			equivocation_proof: sp_consensus_grandpa::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
+			key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(equivocation_proof, key_owner_proof)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: sp_consensus_grandpa::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<sp_consensus_grandpa::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((sp_consensus_grandpa::KEY_TYPE, authority_id))
+				.map(|p| p.encode())
+				.map(sp_consensus_grandpa::OpaqueKeyOwnershipProof::new)
 		}
 	}
 
@@ -508,6 +1016,43 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl apis::AssetTxPaymentApi<Block, AssetId, Balance> for Runtime {
+		fn query_fee_in_asset(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			asset_id: AssetId,
+		) -> Option<Balance> {
+			let fee = TransactionPayment::query_fee_details(uxt, len).final_fee();
+			impls::fee_in_asset(fee, asset_id)
+		}
+	}
+
+	impl apis::TokenApi<Block> for Runtime {
+		fn token_metadata() -> apis::TokenMetadata {
+			impls::token_metadata()
+		}
+	}
+
+	impl apis::SchedulerApi<Block, BlockNumber> for Runtime {
+		fn agenda(when: BlockNumber) -> Vec<apis::AgendaItem<BlockNumber>> {
+			impls::agenda(when)
+		}
+
+		fn pending_agendas() -> Vec<(BlockNumber, Vec<apis::AgendaItem<BlockNumber>>)> {
+			impls::pending_agendas()
+		}
+	}
+
+	impl pallet_template_runtime_api::TemplateApi<Block, AccountId, BlockNumber> for Runtime {
+		fn get_something(who: AccountId) -> Option<u32> {
+			TemplateModule::something(who).map(|info| info.value)
+		}
+
+		fn recent_history() -> Vec<(BlockNumber, AccountId, Option<u32>)> {
+			TemplateModule::history().into_inner()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -575,10 +1120,128 @@ impl_runtime_apis! {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use frame_support::traits::WhitelistedStorageKeys;
-	use sp_core::hexdisplay::HexDisplay;
+	use crate::mock::{new_test_ext, VALIDATORS};
+	use frame_support::{
+		assert_ok,
+		traits::{GenesisBuild, Get, WhitelistedStorageKeys},
+	};
+	use sp_consensus_grandpa::{Equivocation, EquivocationProof, RoundNumber, SetId};
+	use sp_core::{hexdisplay::HexDisplay, H256};
+	use sp_keyring::{Ed25519Keyring, Sr25519Keyring};
 	use std::collections::HashSet;
 
+	/// Two different prevotes of `keyring` in the same round.
+	fn equivocation_proof(
+		set_id: SetId,
+		round: RoundNumber,
+		keyring: Ed25519Keyring,
+	) -> EquivocationProof<Hash, BlockNumber> {
+		let signed_prevote = |target_hash| {
+			let prevote = finality_grandpa::Prevote { target_hash, target_number: 1 };
+			let message = finality_grandpa::Message::Prevote(prevote.clone());
+			let payload = sp_consensus_grandpa::localized_payload(round, set_id, &message);
+			(prevote, keyring.sign(&payload).into())
+		};
+
+		EquivocationProof::new(
+			set_id,
+			Equivocation::Prevote(finality_grandpa::Equivocation {
+				round_number: round,
+				identity: keyring.public().into(),
+				first: signed_prevote(H256::repeat_byte(1)),
+				second: signed_prevote(H256::repeat_byte(2)),
+			}),
+		)
+	}
+
+	#[test]
+	fn grandpa_equivocation_disables_and_removes_the_offender() {
+		new_test_ext().execute_with(|| {
+			let (bob_account, bob_grandpa) = (VALIDATORS[1].0.to_account_id(), VALIDATORS[1].1);
+			let proof = equivocation_proof(Grandpa::current_set_id(), 1, bob_grandpa);
+			let key_owner_proof = Historical::prove((
+				sp_consensus_grandpa::KEY_TYPE,
+				GrandpaId::from(bob_grandpa.public()),
+			))
+			.unwrap();
+
+			assert_ok!(Grandpa::report_equivocation_unsigned(
+				RuntimeOrigin::none(),
+				Box::new(proof.clone()),
+				key_owner_proof.clone(),
+			));
+
+			// Bob can no longer author blocks this session, and leaves the set at the next one.
+			assert_eq!(Session::disabled_validators(), vec![1]);
+			assert!(!ValidatorSet::validators().contains(&bob_account));
+			System::assert_has_event(
+				pallet_validator_set::Event::ValidatorDisabled { who: bob_account }.into(),
+			);
+
+			// The same offence cannot be reported twice.
+			assert!(Grandpa::report_equivocation_unsigned(
+				RuntimeOrigin::none(),
+				Box::new(proof),
+				key_owner_proof,
+			)
+			.is_err());
+		});
+	}
+
+	#[test]
+	fn key_ownership_proof_is_available_for_authorities() {
+		new_test_ext().execute_with(|| {
+			for (_, grandpa) in VALIDATORS {
+				let key = (sp_consensus_grandpa::KEY_TYPE, GrandpaId::from(grandpa.public()));
+				assert!(Historical::prove(key).is_some());
+			}
+			let unknown = GrandpaId::from(Ed25519Keyring::Charlie.public());
+			assert!(Historical::prove((sp_consensus_grandpa::KEY_TYPE, unknown)).is_none());
+		});
+	}
+
+	#[test]
+	fn vesting_schedules_share_one_lock() {
+		let (alice, bob) =
+			(Sr25519Keyring::Alice.to_account_id(), Sr25519Keyring::Bob.to_account_id());
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(alice.clone(), 1 << 60)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+
+		sp_io::TestExternalities::new(t).execute_with(|| {
+			let max_schedules = <Runtime as pallet_vesting::Config>::MAX_VESTING_SCHEDULES;
+			let locked = MinVestedTransfer::get();
+			let schedule =
+				|start| pallet_vesting::VestingInfo::new(locked, EXISTENTIAL_DEPOSIT, start);
+			for start in 1..=max_schedules {
+				assert_ok!(Vesting::vested_transfer(
+					RuntimeOrigin::signed(alice.clone()),
+					bob.clone().into(),
+					schedule(start),
+				));
+			}
+			assert!(Vesting::vested_transfer(
+				RuntimeOrigin::signed(alice.clone()),
+				bob.clone().into(),
+				schedule(max_schedules + 1),
+			)
+			.is_err());
+
+			let locks = Balances::locks(&bob);
+			assert_eq!(locks.len(), 1);
+			assert_eq!(locks[0].amount, max_schedules as Balance * locked);
+		});
+	}
+
+	#[test]
+	fn token_metadata_uses_the_system_address_format() {
+		let token = impls::token_metadata();
+		assert_eq!(token.ss58_format, <Runtime as frame_system::Config>::SS58Prefix::get());
+		assert_eq!(token.symbol, TOKEN_SYMBOL.as_bytes());
+		assert_eq!(token.decimals, TOKEN_DECIMALS);
+	}
+
 	#[test]
 	fn check_whitelist() {
 		let whitelist: HashSet<String> = AllPalletsWithSystem::whitelisted_storage_keys()
@@ -0,0 +1,58 @@
+//! Storage migrations run by `Executive` when the runtime is upgraded.
+
+use crate::Runtime;
+
+/// Migrations for the next runtime upgrade. Each one checks the on-chain storage version of its
+/// pallet, so it is harmless on chains that already ran it.
+pub type Migrations = (pallet_template::migrations::v1::MigrateToV1<Runtime>,);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{System, TemplateModule};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+	use pallet_template::migrations::v0;
+	#[cfg(feature = "try-runtime")]
+	use pallet_template::migrations::v1::MigrateToV1;
+	use sp_core::{bytes::from_hex, storage::Storage};
+
+	/// State in the format written by `node-template export-state` of the original template
+	/// runtime, trimmed to the entries the migration reads. `TemplateModule` has no storage
+	/// version yet and stores the single value 42 in `Something`.
+	const V0_STATE: &str = include_str!("../res/template-v0-state.json");
+
+	fn state_ext(spec: &str) -> sp_io::TestExternalities {
+		let spec: serde_json::Value = serde_json::from_str(spec).unwrap();
+		let top = spec["genesis"]["raw"]["top"]
+			.as_object()
+			.expect("exported state is raw")
+			.iter()
+			.map(|(key, value)| {
+				(from_hex(key).unwrap(), from_hex(value.as_str().unwrap()).unwrap())
+			})
+			.collect();
+		sp_io::TestExternalities::new(Storage { top, children_default: Default::default() })
+	}
+
+	#[test]
+	fn exported_v0_state_migrates_to_v1() {
+		state_ext(V0_STATE).execute_with(|| {
+			assert_eq!(TemplateModule::on_chain_storage_version(), 0);
+			// Upgrades run at the start of the block after the exported one.
+			System::set_block_number(System::block_number() + 1);
+
+			#[cfg(feature = "try-runtime")]
+			{
+				let state = MigrateToV1::<Runtime>::pre_upgrade().unwrap();
+				MigrateToV1::<Runtime>::on_runtime_upgrade();
+				MigrateToV1::<Runtime>::post_upgrade(state).unwrap();
+			}
+			#[cfg(not(feature = "try-runtime"))]
+			Migrations::on_runtime_upgrade();
+
+			assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+			assert_eq!(v0::Something::<Runtime>::get(), None);
+			assert_eq!(pallet_template::Something::<Runtime>::iter().count(), 0);
+		});
+	}
+}
@@ -0,0 +1,42 @@
+//! Test externalities shared by the tests of this runtime.
+
+use crate::{opaque::SessionKeys, Runtime, System};
+use frame_support::traits::GenesisBuild;
+use sp_keyring::{Ed25519Keyring, Sr25519Keyring};
+use sp_runtime::BuildStorage;
+
+/// The genesis validators, with their Aura and GRANDPA keys.
+pub const VALIDATORS: [(Sr25519Keyring, Ed25519Keyring); 2] =
+	[(Sr25519Keyring::Alice, Ed25519Keyring::Alice), (Sr25519Keyring::Bob, Ed25519Keyring::Bob)];
+
+/// Storage at block 1 of a chain with `VALIDATORS` in its first session and an existing treasury
+/// account.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_validator_set::GenesisConfig::<Runtime> {
+		initial_validators: VALIDATORS.iter().map(|(k, _)| k.to_account_id()).collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_session::GenesisConfig::<Runtime> {
+		keys: VALIDATORS
+			.iter()
+			.map(|(aura, grandpa)| {
+				let keys =
+					SessionKeys { aura: aura.public().into(), grandpa: grandpa.public().into() };
+				(aura.to_account_id(), aura.to_account_id(), keys)
+			})
+			.collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	// Maps the genesis authority set to the first session.
+	GenesisBuild::<Runtime>::assimilate_storage(&pallet_grandpa::GenesisConfig::default(), &mut t)
+		.unwrap();
+	// Dust and fee shares below the existential deposit need an existing account.
+	GenesisBuild::<Runtime>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,67 @@
+#!/usr/bin/env bash
+# Checks GRANDPA equivocation reporting on a three-node `local` network.
+#
+# Alice and Bob validate, and a third node runs with Alice's keys as well, so that Alice votes
+# twice whenever the two instances see different blocks. The test passes once the equivocation
+# has been reported to the `Offences` pallet and Alice has been removed from the `ValidatorSet`.
+#
+# Usage: ./scripts/equivocation-test.sh [path to node-template]
+set -euo pipefail
+
+NODE=${1:-./target/release/node-template}
+TIMEOUT=${TIMEOUT:-600}
+BASE=$(mktemp -d)
+PIDS=()
+
+# Storage keys, twox128 of the pallet name followed by twox128 of the storage name.
+OFFENCE_REPORTS=d5c41b52a371aa36c9254ce34324f2a5b262e9238fa402540c250bc3f5d6188d
+VALIDATORS=7d9fe37370ac390779f35763d98106e888dcde934c658227ee1dfafcd6e16903
+ALICE=d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d
+# Peer id of the node key 0x...01 used by Alice.
+BOOTNODE=/ip4/127.0.0.1/tcp/30333/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp
+
+cleanup() {
+	kill "${PIDS[@]}" 2>/dev/null || true
+	wait 2>/dev/null || true
+	rm -rf "$BASE"
+}
+trap cleanup EXIT
+
+start() {
+	local name=$1 port=$2 rpc_port=$3
+	shift 3
+	"$NODE" --chain local --base-path "$BASE/$name" --port "$port" --rpc-port "$rpc_port" "$@" \
+		>"$BASE/$name.log" 2>&1 &
+	PIDS+=($!)
+}
+
+# Queries Bob's node, which never equivocates.
+rpc() {
+	curl -sf -H 'Content-Type: application/json' \
+		-d "{\"id\":1,\"jsonrpc\":\"2.0\",\"method\":\"$1\",\"params\":$2}" \
+		http://127.0.0.1:9945 || true
+}
+
+start alice 30333 9944 --alice \
+	--node-key 0000000000000000000000000000000000000000000000000000000000000001
+start bob 30334 9945 --bob --bootnodes "$BOOTNODE"
+start alice-twin 30335 9946 --alice --bootnodes "$BOOTNODE"
+
+deadline=$((SECONDS + TIMEOUT))
+while [ $SECONDS -lt $deadline ]; do
+	sleep 6
+	reports=$(rpc state_getKeysPaged "[\"0x$OFFENCE_REPORTS\", 1]")
+	validators=$(rpc state_getStorage "[\"0x$VALIDATORS\"]")
+	if [[ $reports == *$OFFENCE_REPORTS* ]] && [[ $validators == *'"result":"0x'* ]] &&
+		[[ $validators != *$ALICE* ]]; then
+		echo "Alice's equivocation was reported and Alice was removed from the validator set."
+		exit 0
+	fi
+done
+
+echo "No equivocation of Alice was punished within $TIMEOUT seconds." >&2
+for name in alice bob alice-twin; do
+	echo "--- last lines of $name.log" >&2
+	tail -n 20 "$BASE/$name.log" >&2
+done
+exit 1